@@ -0,0 +1,128 @@
+use crate::pokemon::POKEMON_VEC;
+use lazy_static::lazy_static;
+use std::collections::BTreeSet;
+use std::fmt;
+
+lazy_static! {
+    /// The name of every ability a species can have.
+    static ref ABILITY_NAMES: BTreeSet<&'static str> = POKEMON_VEC
+        .iter()
+        .flat_map(|species| species.abilities.iter())
+        .map(|name| name.as_str())
+        .collect();
+}
+
+/// An ability a pokemon can have, like Levitate.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ability {
+    name: String,
+}
+
+impl Ability {
+    /// Finds the ability with the name. Only abilities that some species can have are found.
+    pub fn by_name(name: &str) -> Option<Ability> {
+        ABILITY_NAMES.get(name).map(|&name| Ability {
+            name: name.to_string(),
+        })
+    }
+
+    /// Every ability some species can have, sorted by name.
+    pub fn all() -> impl Iterator<Item = Ability> {
+        ABILITY_NAMES.iter().map(|&name| Ability {
+            name: name.to_string(),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl fmt::Display for Ability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// One of the three abilities of a species.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AbilitySlot {
+    First,
+    Second,
+    /// The hidden ability, like the one some pokemon caught in Max Raids have.
+    Hidden,
+}
+
+/// The abilities a species can have. A species with fewer than three abilities repeats them,
+/// like Vaporeon having Water Absorb in both the first and second slot.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AbilitySlots {
+    pub first: Ability,
+    pub second: Ability,
+    pub hidden: Ability,
+}
+
+impl AbilitySlots {
+    /// Makes the slots from the names in the order used by `Species::abilities`. Missing slots
+    /// repeat the first ability.
+    pub(crate) fn from_names(names: &[String]) -> AbilitySlots {
+        let ability = |i: usize| Ability {
+            name: names.get(i).or_else(|| names.first()).cloned().unwrap_or_default(),
+        };
+        AbilitySlots {
+            first: ability(0),
+            second: ability(1),
+            hidden: ability(2),
+        }
+    }
+
+    pub fn get(&self, slot: AbilitySlot) -> &Ability {
+        match slot {
+            AbilitySlot::First => &self.first,
+            AbilitySlot::Second => &self.second,
+            AbilitySlot::Hidden => &self.hidden,
+        }
+    }
+
+    /// The first slot with the ability named **name**.
+    pub fn slot_of(&self, name: &str) -> Option<AbilitySlot> {
+        [AbilitySlot::First, AbilitySlot::Second, AbilitySlot::Hidden]
+            .iter()
+            .copied()
+            .find(|&slot| self.get(slot).name() == name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.slot_of(name).is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pokedex::POKEDEX;
+
+    #[test]
+    fn ability_slots_test() {
+        for species in POKEMON_VEC.iter() {
+            assert_eq!(species.abilities.len(), 3, "{} does not have 3 abilities", species.name());
+        }
+
+        let slots = POKEDEX.species("Toxicroak").unwrap().ability_slots();
+        assert_eq!(slots.first.name(), "Anticipation");
+        assert_eq!(slots.get(AbilitySlot::Hidden).name(), "Poison Touch");
+        assert_eq!(slots.slot_of("Dry Skin"), Some(AbilitySlot::Second));
+        assert!(!slots.contains("Levitate"));
+
+        let slots = AbilitySlots::from_names(&["Levitate".to_string()]);
+        assert_eq!(slots.hidden.name(), "Levitate");
+        assert_eq!(AbilitySlots::from_names(&[]).first.name(), "");
+    }
+
+    #[test]
+    fn ability_lookup_test() {
+        assert_eq!(Ability::by_name("Levitate").map(|ability| ability.to_string()), Some("Levitate".to_string()));
+        assert!(Ability::by_name("Levitates").is_none());
+        assert!(Ability::all().any(|ability| ability.name() == "Wonder Guard"));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::error::{
+    load_tsv,
+    DataError,
+};
+use crate::items::item_name;
+use crate::pokedex::POKEDEX;
+use crate::pokemon::{
+    Nature,
+    Pokemon,
+    Stats,
+};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+const BATTLE_TOWER_TSV: &[u8] = include_bytes!("../../data/raw/sword_shield_battle_tower_pkm_sets.tsv");
+
+/// The level every pokemon is set to in the Battle Tower.
+pub const BATTLE_TOWER_LEVEL: u64 = 50;
+
+/// Loads every set used by trainers in the Battle Tower, returning the first problem found.
+pub fn safe_load_battle_tower() -> Result<Vec<Pokemon>, DataError> {
+    load_tsv(BATTLE_TOWER_TSV, |raw: RawSet| raw.to_pokemon())
+}
+
+lazy_static! {
+    pub static ref BATTLE_TOWER_SETS: Vec<Pokemon> = safe_load_battle_tower()
+        .expect("Could not load the Battle Tower sets");
+}
+
+/// A row of the Battle Tower file, with everything as numeric ids.
+#[derive(Deserialize)]
+struct RawSet {
+    #[serde(rename = "Species")]
+    species: u32,
+    #[serde(rename = "AltForm")]
+    form: u32,
+    #[serde(rename = "HeldItem")]
+    item: u32,
+    #[serde(rename = "Nature")]
+    nature: u32,
+    #[serde(rename = "IV_Hp")]
+    iv_hp: u64,
+    #[serde(rename = "IV_Atk")]
+    iv_attack: u64,
+    #[serde(rename = "IV_Def")]
+    iv_defense: u64,
+    #[serde(rename = "IV_SpAtk")]
+    iv_sp_attack: u64,
+    #[serde(rename = "IV_SpDef")]
+    iv_sp_defense: u64,
+    #[serde(rename = "IV_Spe")]
+    iv_speed: u64,
+    #[serde(rename = "Move0")]
+    move0: u32,
+    #[serde(rename = "Move1")]
+    move1: u32,
+    #[serde(rename = "Move2")]
+    move2: u32,
+    #[serde(rename = "Move3")]
+    move3: u32,
+}
+
+impl RawSet {
+    /// Resolves the ids, returning a message for the first unknown one.
+    fn to_pokemon(&self) -> Result<Pokemon, String> {
+        let species = POKEDEX
+            .national_dex_form(self.species, self.form)
+            .ok_or_else(|| format!("unknown species {} form {}", self.species, self.form))?;
+        let nature = Nature::from_index(self.nature)
+            .ok_or_else(|| format!("unknown nature {}", self.nature))?;
+
+        let mut move_list = Vec::new();
+        for &id in [self.move0, self.move1, self.move2, self.move3].iter().filter(|&&id| id != 0) {
+            let mv = POKEDEX.move_by_id(id).ok_or_else(|| format!("unknown move {}", id))?;
+            move_list.push(mv.clone());
+        }
+
+        let ivs = Stats {
+            hp: self.iv_hp,
+            attack: self.iv_attack,
+            defense: self.iv_defense,
+            sp_attack: self.iv_sp_attack,
+            sp_defense: self.iv_sp_defense,
+            speed: self.iv_speed,
+        };
+        let pokemon = Pokemon::new(species, BATTLE_TOWER_LEVEL, move_list)
+            .with_nature(nature)
+            .with_ivs(ivs);
+
+        match self.item {
+            0 => Ok(pokemon),
+            id => {
+                let item = item_name(id).ok_or_else(|| format!("unknown item {}", id))?;
+                Ok(pokemon.with_item(item))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn load_battle_tower_test() {
+        let sets = safe_load_battle_tower().expect("Could not load the Battle Tower sets");
+        assert_eq!(sets.len(), 922);
+
+        let rillaboom = &sets[0];
+        assert_eq!(rillaboom.species_name(), "Rillaboom");
+        assert_eq!(rillaboom.level(), 50);
+        assert_eq!(rillaboom.item(), Some("Assault Vest"));
+        assert_eq!(rillaboom.nature(), Nature::Modest);
+        assert_eq!(rillaboom.ivs().hp, 16);
+        assert_eq!(rillaboom.moves().len(), 4);
+    }
+}
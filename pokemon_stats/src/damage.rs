@@ -0,0 +1,451 @@
+use crate::moves::{
+    Category,
+    Move,
+};
+use crate::pokemon::{
+    Efficacy,
+    Pokemon,
+};
+use std::fmt;
+
+/// Number of random rolls, from 85% to 100% of the damage.
+pub const ROLL_COUNT: usize = 16;
+
+/// Things about a single attack that are not part of either pokemon.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Conditions {
+    pub critical_hit: bool,
+    /// The attacker is burned, halving the damage of physical moves.
+    pub burned: bool,
+}
+
+/// The damage of a single hit, for every random roll.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Damage {
+    /// The damage for each random roll, from lowest to highest.
+    pub rolls: [u64; ROLL_COUNT],
+    /// The max hp of the defender.
+    pub defender_hp: u64,
+}
+
+impl Damage {
+    pub fn min(&self) -> u64 {
+        self.rolls[0]
+    }
+
+    pub fn max(&self) -> u64 {
+        self.rolls[ROLL_COUNT - 1]
+    }
+
+    /// Each roll as a percent of the defender's max hp.
+    pub fn percents(&self) -> [f64; ROLL_COUNT] {
+        let mut percents = [0.0; ROLL_COUNT];
+        for (percent, &roll) in percents.iter_mut().zip(self.rolls.iter()) {
+            *percent = roll as f64 * 100.0 / self.defender_hp as f64;
+        }
+        percents
+    }
+
+    pub fn min_percent(&self) -> f64 {
+        self.percents()[0]
+    }
+
+    pub fn max_percent(&self) -> f64 {
+        self.percents()[ROLL_COUNT - 1]
+    }
+}
+
+/// Calculates the damage of one hit of **mv** using the generation 8 damage formula.
+///
+/// Covers the level, the attack and defense stats picked by the move category, STAB, type
+/// effectiveness, critical hits, burn, Choice Band and Choice Specs. The defender's ability is
+/// included through `Pokemon::move_efficacy` and `Pokemon::damage_modifier`. Other items,
+/// abilities, weather and stat stages are not taken into account.
+///
+/// Returns None for status moves, for moves without a fixed power, like Low Kick, and for moves
+/// the defender is immune to, like Earthquake against a pokemon with Levitate.
+pub fn damage(attacker: &Pokemon, defender: &Pokemon, mv: &Move, conditions: Conditions) -> Option<Damage> {
+    if !mv.is_attack() || mv.power == 0 {
+        return None;
+    }
+    let efficacy = defender.move_efficacy(mv);
+    if efficacy == Efficacy::Zero {
+        return None;
+    }
+
+    let (attack, defense) = attack_and_defense(attacker, defender, mv);
+    let level = attacker.level();
+    let base = (2 * level / 5 + 2) * mv.power as u64 * attack / defense.max(1) / 50 + 2;
+    let base = if conditions.critical_hit { base * 3 / 2 } else { base };
+    let stab = attacker.species().types.contains(mv.move_type);
+    let burned = conditions.burned && mv.category == Category::Physical && attacker.ability() != "Guts";
+    let modifier = defender.damage_modifier(mv);
+
+    let mut rolls = [0; ROLL_COUNT];
+    for (i, roll) in rolls.iter_mut().enumerate() {
+        let mut damage = base * (85 + i as u64) / 100;
+        if stab {
+            damage = apply_modifier(damage, 6144);
+        }
+        damage = match efficacy {
+            Efficacy::Pow2(n) if n >= 0 => damage << n,
+            Efficacy::Pow2(n) => damage >> -n,
+            Efficacy::Zero => 0,
+        };
+        if burned {
+            damage /= 2;
+        }
+        damage = apply_modifier(damage, modifier);
+        *roll = damage.max(1);
+    }
+
+    Some(Damage {
+        rolls,
+        defender_hp: defender.stats().hp,
+    })
+}
+
+/// The chance of the move landing a critical hit, from its critical hit stage. Items and
+/// abilities that raise the stage are not taken into account.
+pub fn crit_chance(mv: &Move) -> f64 {
+    match mv.crit_stage {
+        0 => 1.0 / 24.0,
+        1 => 1.0 / 8.0,
+        2 => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// The chance of the move hitting, ignoring accuracy and evasion stages. Moves that never miss
+/// have an accuracy of 101.
+pub fn accuracy_chance(mv: &Move) -> f64 {
+    mv.accuracy.min(100) as f64 / 100.0
+}
+
+/// The chance of each number of hits for one use of the move.
+///
+/// Moves that hit 2 to 5 times hit 2 or 3 times 35% of the time each, and 4 or 5 times 15% of
+/// the time each. With Skill Link they always hit 5 times. Other moves with a range of hits,
+/// like Triple Kick, are treated as hitting each number of times equally often, with the same
+/// power every hit.
+pub fn hit_count_chances(attacker: &Pokemon, mv: &Move) -> Vec<(u8, f64)> {
+    match (mv.hit_min, mv.hit_max) {
+        (2, 5) if attacker.ability() == "Skill Link" => vec![(5, 1.0)],
+        (2, 5) => vec![(2, 0.35), (3, 0.35), (4, 0.15), (5, 0.15)],
+        (min, max) if min < max => {
+            let chance = 1.0 / (max - min + 1) as f64;
+            (min..=max).map(|hits| (hits, chance)).collect()
+        }
+        (_, max) => vec![(max.max(1), 1.0)],
+    }
+}
+
+/// The chance of each amount of total damage done to a defender over several moves. Damage
+/// past the defender's hp is counted as exactly its hp.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DamageDistribution {
+    /// The chance of each amount of damage, indexed by the damage.
+    chances: Vec<f64>,
+}
+
+impl DamageDistribution {
+    /// No damage done yet to a defender with **defender_hp**.
+    pub fn new(defender_hp: u64) -> Self {
+        let mut chances = vec![0.0; defender_hp as usize + 1];
+        chances[0] = 1.0;
+        DamageDistribution {
+            chances,
+        }
+    }
+
+    /// Adds one use of **mv**, including the chance of missing, of critical hits and of each
+    /// number of hits. When `conditions.critical_hit` is set every hit is a critical hit.
+    pub fn add_move(&mut self, attacker: &Pokemon, defender: &Pokemon, mv: &Move, conditions: Conditions) {
+        let crit_chance = if conditions.critical_hit { 1.0 } else { crit_chance(mv) };
+        self.add_move_with_crit_chance(attacker, defender, mv, conditions, crit_chance);
+    }
+
+    fn add_move_with_crit_chance(
+        &mut self,
+        attacker: &Pokemon,
+        defender: &Pokemon,
+        mv: &Move,
+        conditions: Conditions,
+        crit_chance: f64,
+    ) {
+        let normal = Conditions { critical_hit: false, ..conditions };
+        let crit = Conditions { critical_hit: true, ..conditions };
+        let (normal, crit) = match (damage(attacker, defender, mv, normal), damage(attacker, defender, mv, crit)) {
+            (Some(normal), Some(crit)) => (normal, crit),
+            _ => return,
+        };
+
+        // the damage of a single hit, with the chance of each roll
+        let roll_chance = 1.0 / ROLL_COUNT as f64;
+        let hit: Vec<(u64, f64)> = normal
+            .rolls
+            .iter()
+            .map(|&roll| (roll, (1.0 - crit_chance) * roll_chance))
+            .chain(crit.rolls.iter().map(|&roll| (roll, crit_chance * roll_chance)))
+            .collect();
+
+        let accuracy = accuracy_chance(mv);
+        let mut result: Vec<f64> = self.chances.iter().map(|chance| chance * (1.0 - accuracy)).collect();
+        let mut after_hits = self.chances.clone();
+        let mut hits_done = 0;
+        for (hits, chance) in hit_count_chances(attacker, mv) {
+            while hits_done < hits {
+                after_hits = add_hit(&after_hits, &hit);
+                hits_done += 1;
+            }
+            for (total, after) in result.iter_mut().zip(after_hits.iter()) {
+                *total += accuracy * chance * after;
+            }
+        }
+        self.chances = result;
+    }
+
+    /// The chance of the total damage being at least the defender's hp.
+    pub fn ko_chance(&self) -> f64 {
+        self.chances[self.chances.len() - 1]
+    }
+
+    /// The chance of each amount of damage, indexed by the damage.
+    pub fn chances(&self) -> &[f64] {
+        self.chances.as_slice()
+    }
+}
+
+/// Adds the damage of one hit to **before**, the chance of each amount of damage so far.
+fn add_hit(before: &[f64], hit: &[(u64, f64)]) -> Vec<f64> {
+    let hp = before.len() - 1;
+    let mut after = vec![0.0; before.len()];
+    for (done, &chance) in before.iter().enumerate().filter(|&(_, &chance)| chance > 0.0) {
+        for &(damage, hit_chance) in hit.iter() {
+            let total = (done + damage as usize).min(hp);
+            after[total] += chance * hit_chance;
+        }
+    }
+    after
+}
+
+/// The chance of the moves, used in order, knocking out the defender from full hp.
+pub fn ko_chance(attacker: &Pokemon, defender: &Pokemon, moves: &[&Move], conditions: Conditions) -> f64 {
+    let mut distribution = DamageDistribution::new(defender.stats().hp);
+    for mv in moves.iter() {
+        distribution.add_move(attacker, defender, mv, conditions);
+    }
+    distribution.ko_chance()
+}
+
+/// The fewest uses of a move that can knock out the defender, and the chance that they do.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KoChance {
+    pub uses: usize,
+    pub chance: f64,
+    /// The move can not miss and its lowest roll knocks out in **uses**, so the knock out does
+    /// not depend on luck. Worked out from the damage rather than **chance** to not be thrown
+    /// off by rounding.
+    pub guaranteed: bool,
+}
+
+impl fmt::Display for KoChance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ko = match self.uses {
+            1 => "OHKO".to_string(),
+            uses => format!("{}HKO", uses),
+        };
+        if self.guaranteed {
+            write!(f, "guaranteed {}", ko)
+        } else {
+            write!(f, "{:.1}% chance to {}", self.chance * 100.0, ko)
+        }
+    }
+}
+
+/// The fewest uses of **mv** that can knock out the defender from full hp, like a 63.2% chance
+/// to 2HKO. Returns None if the move does no damage, like `damage`.
+///
+/// Critical hits are left out when finding the number of uses, unless `conditions.critical_hit`
+/// is set, so a move that only knocks out in one use with a critical hit is a 2HKO. The chance
+/// of that many uses knocking out still includes critical hits.
+pub fn hits_to_ko(attacker: &Pokemon, defender: &Pokemon, mv: &Move, conditions: Conditions) -> Option<KoChance> {
+    let least = damage(attacker, defender, mv, conditions)?.min();
+    let hp = defender.stats().hp;
+    let least_hits = hit_count_chances(attacker, mv).iter().map(|&(hits, _)| hits as u64).min().unwrap_or(1);
+    let never_misses = mv.accuracy >= 100;
+    let crit_chance = if conditions.critical_hit { 1.0 } else { 0.0 };
+    let mut without_crits = DamageDistribution::new(hp);
+    let mut distribution = DamageDistribution::new(hp);
+    // every use that hits does at least 1 damage
+    for uses in 1..=hp as usize {
+        without_crits.add_move_with_crit_chance(attacker, defender, mv, conditions, crit_chance);
+        distribution.add_move(attacker, defender, mv, conditions);
+        if without_crits.ko_chance() > 0.0 {
+            return Some(KoChance {
+                uses,
+                chance: distribution.ko_chance(),
+                guaranteed: never_misses && least * least_hits * uses as u64 >= hp,
+            });
+        }
+    }
+    None
+}
+
+/// The attacking and defending stats used by the move.
+fn attack_and_defense(attacker: &Pokemon, defender: &Pokemon, mv: &Move) -> (u64, u64) {
+    let attacker_stats = attacker.stats();
+    let defender_stats = defender.stats();
+    let physical = mv.category == Category::Physical;
+    let attack = match mv.name() {
+        "Body Press" => attacker_stats.defense,
+        "Foul Play" => defender_stats.attack,
+        _ if physical => attacker_stats.attack,
+        _ => attacker_stats.sp_attack,
+    };
+    let defense = match mv.name() {
+        "Psyshock" | "Psystrike" | "Secret Sword" => defender_stats.defense,
+        _ if physical => defender_stats.defense,
+        _ => defender_stats.sp_defense,
+    };
+
+    let attack = match attacker.item() {
+        Some("Choice Band") if physical => apply_modifier(attack, 6144),
+        Some("Choice Specs") if !physical => apply_modifier(attack, 6144),
+        _ => attack,
+    };
+    (attack, defense)
+}
+
+/// Multiplies **value** by **modifier** / 4096, rounding halves down like the games do.
+fn apply_modifier(value: u64, modifier: u64) -> u64 {
+    (value * modifier + 2047) / 4096
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pokedex::POKEDEX;
+    use crate::pokemon::Stats;
+
+    fn pokemon(species: &str, moves: &[&str]) -> Pokemon {
+        let species = POKEDEX.species(species).unwrap();
+        let moves = moves.iter().map(|name| POKEDEX.move_by_name(name).unwrap().clone()).collect();
+        Pokemon::new(species, 50, moves)
+    }
+
+    #[test]
+    fn damage_test() {
+        let excadrill = pokemon("Excadrill", &["Earthquake", "Swords Dance"]);
+        let toxapex = pokemon("Toxapex", &[]);
+        let earthquake = &excadrill.moves()[0];
+        let result = damage(&excadrill, &toxapex, earthquake, Conditions::default()).unwrap();
+        // level 50, 100 power, 155 Attack into 172 Defense, STAB and super effective
+        assert_eq!(result.rolls, [102, 104, 104, 108, 108, 108, 110, 110, 114, 114, 114, 116, 116, 120, 120, 122]);
+        assert_eq!(result.defender_hp, 125);
+        assert!((result.max_percent() - 97.6).abs() < 1e-9);
+        for pair in result.rolls.windows(2) {
+            assert!(pair[0] <= pair[1]);
+        }
+
+        assert!(damage(&excadrill, &toxapex, &excadrill.moves()[1], Conditions::default()).is_none());
+
+        let corviknight = pokemon("Corviknight", &[]);
+        assert!(damage(&excadrill, &corviknight, earthquake, Conditions::default()).is_none());
+    }
+
+    #[test]
+    fn damage_abilities_test() {
+        let excadrill = pokemon("Excadrill", &["Earthquake", "Rock Slide"]);
+        let earthquake = &excadrill.moves()[0];
+        let rotom = pokemon("Rotom 2", &[]).with_ability("Levitate");
+        assert!(damage(&excadrill, &rotom, earthquake, Conditions::default()).is_none());
+        assert!(hits_to_ko(&excadrill, &rotom, earthquake, Conditions::default()).is_none());
+
+        // Solid Rock takes 3072 / 4096 of the super effective damage
+        let rock_slide = &excadrill.moves()[1];
+        let butterfree = pokemon("Butterfree", &[]);
+        let normal = damage(&excadrill, &butterfree, rock_slide, Conditions::default()).unwrap();
+        let solid_rock = butterfree.clone().with_ability("Solid Rock");
+        let reduced = damage(&excadrill, &solid_rock, rock_slide, Conditions::default()).unwrap();
+        for (&normal, &reduced) in normal.rolls.iter().zip(reduced.rolls.iter()) {
+            assert_eq!(reduced, apply_modifier(normal, 3072));
+        }
+    }
+
+    #[test]
+    fn damage_conditions_test() {
+        let excadrill = pokemon("Excadrill", &["Earthquake"])
+            .with_evs(Stats { attack: 252, ..Stats::zeros() });
+        let toxapex = pokemon("Toxapex", &[]);
+        let earthquake = &excadrill.moves()[0];
+        let normal = damage(&excadrill, &toxapex, earthquake, Conditions::default()).unwrap();
+
+        let crit = Conditions { critical_hit: true, ..Conditions::default() };
+        let crit = damage(&excadrill, &toxapex, earthquake, crit).unwrap();
+        assert!(crit.min() > normal.min());
+
+        let burned = Conditions { burned: true, ..Conditions::default() };
+        let burned = damage(&excadrill, &toxapex, earthquake, burned).unwrap();
+        assert_eq!(burned.max(), normal.max() / 2);
+
+        let banded = excadrill.clone().with_item("Choice Band");
+        let banded = damage(&banded, &toxapex, earthquake, Conditions::default()).unwrap();
+        assert!(banded.min() > normal.min());
+    }
+
+    #[test]
+    fn hit_chances_test() {
+        let mv = |name| POKEDEX.move_by_name(name).unwrap();
+        assert!((crit_chance(mv("Earthquake")) - 1.0 / 24.0).abs() < 1e-9);
+        assert!((crit_chance(mv("Slash")) - 1.0 / 8.0).abs() < 1e-9);
+        assert_eq!(crit_chance(mv("Frost Breath")), 1.0);
+        assert_eq!(accuracy_chance(mv("Rock Blast")), 0.9);
+        assert_eq!(accuracy_chance(mv("Aerial Ace")), 1.0);
+
+        let cloyster = pokemon("Cloyster", &[]);
+        assert_eq!(hit_count_chances(&cloyster, mv("Earthquake")), vec![(1, 1.0)]);
+        assert_eq!(hit_count_chances(&cloyster, mv("Dragon Darts")), vec![(2, 1.0)]);
+        let chances = hit_count_chances(&cloyster, mv("Icicle Spear"));
+        assert_eq!(chances.len(), 4);
+        assert!((chances.iter().map(|(_, chance)| chance).sum::<f64>() - 1.0).abs() < 1e-9);
+        let skill_link = cloyster.with_ability("Skill Link");
+        assert_eq!(hit_count_chances(&skill_link, mv("Icicle Spear")), vec![(5, 1.0)]);
+    }
+
+    #[test]
+    fn ko_chance_test() {
+        let excadrill = pokemon("Excadrill", &["Earthquake", "Swords Dance"]);
+        let toxapex = pokemon("Toxapex", &[]);
+        let earthquake = &excadrill.moves()[0];
+        let conditions = Conditions::default();
+
+        // only a critical hit knocks out in one use
+        let one = ko_chance(&excadrill, &toxapex, &[earthquake], conditions);
+        assert!((one - 1.0 / 24.0).abs() < 1e-9);
+        assert!((ko_chance(&excadrill, &toxapex, &[earthquake, earthquake], conditions) - 1.0).abs() < 1e-9);
+
+        // the critical hit OHKO is left out, leaving a certain 2HKO
+        let ko = hits_to_ko(&excadrill, &toxapex, earthquake, conditions).unwrap();
+        assert_eq!(ko.uses, 2);
+        assert!(ko.guaranteed);
+        assert_eq!(ko.to_string(), "guaranteed 2HKO");
+        let crit = Conditions { critical_hit: true, ..conditions };
+        let ko = hits_to_ko(&excadrill, &toxapex, earthquake, crit).unwrap();
+        assert_eq!(ko.uses, 1);
+        assert_eq!(ko.to_string(), "guaranteed OHKO");
+        assert!(hits_to_ko(&excadrill, &toxapex, &excadrill.moves()[1], conditions).is_none());
+
+        // Rock Blast can miss, and two hits only knock out with high rolls
+        let cloyster = pokemon("Cloyster", &["Rock Blast"]);
+        let butterfree = pokemon("Butterfree", &[]);
+        let rock_blast = &cloyster.moves()[0];
+        let chance = ko_chance(&cloyster, &butterfree, &[rock_blast], conditions);
+        assert!(chance > 0.9 * 0.65 && chance < 0.9);
+        let skill_link = cloyster.clone().with_ability("Skill Link");
+        assert!((ko_chance(&skill_link, &butterfree, &[rock_blast], conditions) - 0.9).abs() < 1e-9);
+        let ko = hits_to_ko(&skill_link, &butterfree, rock_blast, conditions).unwrap();
+        assert!(!ko.guaranteed);
+        assert_eq!(ko.to_string(), "90.0% chance to OHKO");
+    }
+}
@@ -0,0 +1,799 @@
+use crate::error::{
+    load_tsv,
+    DataError,
+};
+use crate::pokedex::POKEDEX;
+use crate::evolution::Evolution;
+use crate::items::Item;
+use crate::pokemon::{
+    form_name,
+    Gender,
+    Move,
+    Nature,
+    Pokemon,
+    Species,
+    Stats,
+};
+use crate::raids::{
+    raid_dens,
+    Nest,
+    RaidDen,
+    SHIELD_NESTS,
+    SWORD_NESTS,
+};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+const SWORD_OVERWORLD: &str = include_str!("../../data/raw/sword_overworld_encounter_tables.txt");
+const SWORD_HIDDEN: &str = include_str!("../../data/raw/sword_non-overworld_encounter_tables.txt");
+const SHIELD_OVERWORLD: &str = include_str!("../../data/raw/shield_overworld_encounter_tables.txt");
+const SHIELD_HIDDEN: &str = include_str!("../../data/raw/shield_non-overworld_encounter_tables.txt");
+const STATIC_ENCOUNTERS_TSV: &[u8] = include_bytes!("../../data/raw/sword_shield_static_encounters.tsv");
+
+/// Loads every wild encounter location for the game, returning the parsing error.
+pub fn safe_load_locations(version: GameVersion) -> Result<Vec<Location>, DataError> {
+    let (overworld, hidden) = match version {
+        GameVersion::Sword => (SWORD_OVERWORLD, SWORD_HIDDEN),
+        GameVersion::Shield => (SHIELD_OVERWORLD, SHIELD_HIDDEN),
+    };
+
+    let mut locations = parse_locations(overworld, EncounterKind::Overworld)?;
+    locations.append(&mut parse_locations(hidden, EncounterKind::Hidden)?);
+    Ok(locations)
+}
+
+/// Loads every static encounter in the games, returning the first problem found. This includes
+/// scripted battles that can not end in a catch, see `StaticEncounter::catchable`.
+pub fn safe_load_static_encounters() -> Result<Vec<StaticEncounter>, DataError> {
+    load_tsv(STATIC_ENCOUNTERS_TSV, |raw: RawStaticEncounter| StaticEncounter::try_from(raw))
+}
+
+lazy_static! {
+    pub static ref STATIC_ENCOUNTERS: Vec<StaticEncounter> = safe_load_static_encounters()
+        .expect("Could not load static encounters");
+    pub static ref SWORD_LOCATIONS: Vec<Location> = safe_load_locations(GameVersion::Sword)
+        .expect("Could not load Sword encounters");
+    pub static ref SHIELD_LOCATIONS: Vec<Location> = safe_load_locations(GameVersion::Shield)
+        .expect("Could not load Shield encounters");
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GameVersion {
+    Sword,
+    Shield,
+}
+
+impl GameVersion {
+    /// Every wild encounter location in this version.
+    pub fn locations(self) -> &'static [Location] {
+        match self {
+            GameVersion::Sword => &SWORD_LOCATIONS,
+            GameVersion::Shield => &SHIELD_LOCATIONS,
+        }
+    }
+
+    /// Every Max Raid nest in this version.
+    pub fn nests(self) -> &'static [Nest] {
+        match self {
+            GameVersion::Sword => &SWORD_NESTS,
+            GameVersion::Shield => &SHIELD_NESTS,
+        }
+    }
+}
+
+/// The overworld weather an encounter table is used in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weather {
+    /// The table is used in any weather.
+    All,
+    Normal,
+    Overcast,
+    Raining,
+    Thunderstorm,
+    IntenseSun,
+    Snowing,
+    Snowstorm,
+    Sandstorm,
+    HeavyFog,
+}
+
+/// How the pokemon in an encounter table are found.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EncounterKind {
+    /// Visible pokemon wandering the overworld.
+    Overworld,
+    /// Pokemon hiding in tall grass, shown by an "!".
+    Hidden,
+    Fishing,
+    ShakingTrees,
+}
+
+/// A place with wild pokemon.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+    /// The index of the location in its encounter file. Overworld and hidden encounters are
+    /// numbered separately, so the same place can have two ids.
+    pub id: u32,
+    pub name: String,
+    pub tables: Vec<EncounterTable>,
+}
+
+/// The pokemon that can appear in one location under one condition.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncounterTable {
+    pub kind: EncounterKind,
+    pub weather: Weather,
+    pub min_level: u8,
+    pub max_level: u8,
+    pub slots: Vec<EncounterSlot>,
+}
+
+impl Location {
+    /// Every encounter in this location with a species that can hold the item, like the
+    /// pokemon worth using Thief on for a Leftovers.
+    pub fn item_holders(&self, item: &Item) -> Vec<WildItemHolder<'_>> {
+        let mut found = Vec::new();
+        for table in self.tables.iter() {
+            for slot in table.slots.iter() {
+                let held = slot
+                    .species()
+                    .map(|species| species.wild_held_items())
+                    .unwrap_or_default();
+                if let Some(held) = held.iter().find(|held| held.item.id == item.id) {
+                    found.push(WildItemHolder {
+                        encounter: WildEncounter {
+                            location: self,
+                            table,
+                            slot,
+                        },
+                        chance: held.chance,
+                    });
+                }
+            }
+        }
+
+        found
+    }
+}
+
+/// One species in an encounter table and how often it appears.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncounterSlot {
+    /// The name of the species, as used by `Species::name`.
+    pub species: String,
+    /// The chance of this slot being picked, out of 100.
+    pub percent: u8,
+}
+
+impl EncounterSlot {
+    pub fn species(&self) -> Option<&'static Species> {
+        POKEDEX.species(&self.species)
+    }
+}
+
+/// A single way to find a species in the wild.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WildEncounter<'a> {
+    pub location: &'a Location,
+    pub table: &'a EncounterTable,
+    pub slot: &'a EncounterSlot,
+}
+
+/// Every place the species can be found in the wild in the given version.
+pub fn wild_encounters(version: GameVersion, species: &Species) -> Vec<WildEncounter<'static>> {
+    let mut found = Vec::new();
+    for location in version.locations() {
+        for table in location.tables.iter() {
+            for slot in table.slots.iter().filter(|slot| slot.species == species.name()) {
+                found.push(WildEncounter {
+                    location,
+                    table,
+                    slot,
+                });
+            }
+        }
+    }
+
+    found
+}
+
+/// A wild encounter with a species that can hold an item.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WildItemHolder<'a> {
+    pub encounter: WildEncounter<'a>,
+    /// The chance of the pokemon holding the item, out of 100.
+    pub chance: u8,
+}
+
+/// Every wild encounter in the given version with a species that can hold the item.
+pub fn wild_item_holders(version: GameVersion, item: &Item) -> Vec<WildItemHolder<'static>> {
+    version
+        .locations()
+        .iter()
+        .flat_map(|location| location.item_holders(item))
+        .collect()
+}
+
+/// A pokemon found at a fixed place and level, like the legendaries or the pokemon guarding
+/// items in the Wild Area.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "RawStaticEncounter")]
+pub struct StaticEncounter {
+    /// The national dex number of the species.
+    pub national_dex: u32,
+    pub form: u32,
+    pub level: u8,
+    /// The fixed nature, or None when it is random.
+    pub nature: Option<Nature>,
+    /// The fixed gender, or None when it is random.
+    pub gender: Option<Gender>,
+    pub ability: StaticAbility,
+    pub ivs: StaticIvs,
+    pub evs: Stats,
+    pub dynamax_level: u8,
+    pub item: Option<&'static Item>,
+    /// The moves known. When empty, the pokemon knows its `Species::default_moves`.
+    pub moves: Vec<Move>,
+    /// Whether the pokemon can never be shiny.
+    pub shiny_locked: bool,
+    /// Whether the encounter can end in a catch. Scripted battles, like the rampaging Dynamax
+    /// pokemon after the story, can not.
+    pub catchable: bool,
+}
+
+/// Which ability a static encounter has, from the abilities of its species.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StaticAbility {
+    /// Either of the regular abilities, picked at random.
+    Random,
+    First,
+    Second,
+    Hidden,
+}
+
+/// The IVs of a static encounter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StaticIvs {
+    /// Random IVs, with at least **perfect** of them at 31.
+    Random { perfect: u8 },
+    Fixed(Stats),
+}
+
+impl StaticEncounter {
+    pub fn species(&self) -> Option<&'static Species> {
+        POKEDEX.national_dex_form(self.national_dex, self.form)
+    }
+
+    /// The name of the fixed ability, or None when it is picked at random.
+    pub fn ability_name(&self) -> Option<&'static str> {
+        let slot = match self.ability {
+            StaticAbility::Random => return None,
+            StaticAbility::First => 0,
+            StaticAbility::Second => 1,
+            StaticAbility::Hidden => 2,
+        };
+        self.species()?.abilities.get(slot).map(|ability| ability.as_str())
+    }
+
+    /// The pokemon as it is encountered, with every fixed value filled in. Random values, like
+    /// random IVs, are left at the defaults of `Pokemon::new`.
+    pub fn to_pokemon(&self) -> Option<Pokemon> {
+        let species = self.species()?;
+        let level = u64::from(self.level);
+        let moves = if self.moves.is_empty() {
+            species.default_moves(level).into_iter().cloned().collect()
+        } else {
+            self.moves.clone()
+        };
+
+        let mut pokemon = Pokemon::new(species, level, moves)
+            .with_evs(self.evs)
+            .with_dynamax_level(self.dynamax_level);
+        if let StaticIvs::Fixed(ivs) = self.ivs {
+            pokemon = pokemon.with_ivs(ivs);
+        }
+        if let Some(nature) = self.nature {
+            pokemon = pokemon.with_nature(nature);
+        }
+        if let Some(gender) = self.gender {
+            pokemon = pokemon.with_gender(gender);
+        }
+        if let Some(ability) = self.ability_name() {
+            pokemon = pokemon.with_ability(ability);
+        }
+        if let Some(item) = self.item {
+            pokemon = pokemon.with_item(item.name());
+        }
+        Some(pokemon)
+    }
+
+    /// Whether the encounter appears in the version.
+    ///
+    /// The data does not say which version each encounter is in, so every encounter is assumed
+    /// to be in both, except for the version exclusive legendaries Zacian and Zamazenta.
+    pub fn is_in(&self, version: GameVersion) -> bool {
+        !matches!(
+            (self.national_dex, version),
+            (888, GameVersion::Shield) | (889, GameVersion::Sword)
+        )
+    }
+}
+
+/// A row of the static encounter file, with everything as numeric ids.
+#[derive(Deserialize)]
+struct RawStaticEncounter {
+    #[serde(rename = "Species")]
+    species: u32,
+    #[serde(rename = "AltForm")]
+    form: u32,
+    #[serde(rename = "Level")]
+    level: u8,
+    /// 25 for a random nature.
+    #[serde(rename = "Nature")]
+    nature: u32,
+    /// 0 for random, 1 for male and 2 for female.
+    #[serde(rename = "Gender")]
+    gender: u8,
+    /// 0 for random, then 1 to 3 for the first, second and hidden ability.
+    #[serde(rename = "Ability")]
+    ability: u8,
+    /// 2 when the pokemon can not be shiny.
+    #[serde(rename = "ShinyLock")]
+    shiny_lock: u8,
+    #[serde(rename = "HeldItem")]
+    item: u32,
+    #[serde(rename = "DynamaxLevel")]
+    dynamax_level: u8,
+    /// Negative IVs are random. The number of perfect IVs is one less than the largest
+    /// negative value, so -1 is fully random and -4 has three perfect IVs.
+    #[serde(rename = "IV_Hp")]
+    iv_hp: i8,
+    #[serde(rename = "IV_Atk")]
+    iv_attack: i8,
+    #[serde(rename = "IV_Def")]
+    iv_defense: i8,
+    #[serde(rename = "IV_SpAtk")]
+    iv_sp_attack: i8,
+    #[serde(rename = "IV_SpDef")]
+    iv_sp_defense: i8,
+    #[serde(rename = "IV_Spe")]
+    iv_speed: i8,
+    #[serde(rename = "EV_Hp")]
+    ev_hp: u64,
+    #[serde(rename = "EV_Atk")]
+    ev_attack: u64,
+    #[serde(rename = "EV_Def")]
+    ev_defense: u64,
+    #[serde(rename = "EV_SpAtk")]
+    ev_sp_attack: u64,
+    #[serde(rename = "EV_SpDef")]
+    ev_sp_defense: u64,
+    #[serde(rename = "EV_Spe")]
+    ev_speed: u64,
+    #[serde(rename = "Move0")]
+    move0: u32,
+    #[serde(rename = "Move1")]
+    move1: u32,
+    #[serde(rename = "Move2")]
+    move2: u32,
+    #[serde(rename = "Move3")]
+    move3: u32,
+    /// An unnamed column grouping scripted encounters, see `is_catchable`.
+    #[serde(rename = "Field_0F")]
+    scenario: u8,
+}
+
+impl RawStaticEncounter {
+    /// Whether the encounter can end in a catch, going by the rows known to be scripted battles:
+    ///
+    /// * Eternamax Eternatus, which is only fought. The regular form is caught right after.
+    /// * Zacian and Zamazenta, except for the level 70 encounter with scenario 1 where they are
+    ///   caught. Their other rows are story battles: the level 1 ones in the Slumbering Weald
+    ///   fog, the level 55 ones with a fixed nature and the level 70 one fought after the story.
+    /// * The level 62 rampaging Dynamax pokemon after the story, with scenarios 9 to 12.
+    fn is_catchable(&self) -> bool {
+        match (self.species, self.form, self.scenario) {
+            (890, 1, _) => false,
+            (888, _, scenario) | (889, _, scenario) => scenario == 1,
+            (_, _, 9..=12) => false,
+            _ => true,
+        }
+    }
+}
+
+impl TryFrom<RawStaticEncounter> for StaticEncounter {
+    type Error = String;
+
+    fn try_from(raw: RawStaticEncounter) -> Result<Self, Self::Error> {
+        let catchable = raw.is_catchable();
+        let nature = match raw.nature {
+            25 => None,
+            index => Some(Nature::from_index(index).ok_or_else(|| format!("unknown nature {}", index))?),
+        };
+        let gender = match raw.gender {
+            0 => None,
+            1 => Some(Gender::Male),
+            2 => Some(Gender::Female),
+            gender => return Err(format!("unknown gender {}", gender)),
+        };
+        let ability = match raw.ability {
+            0 => StaticAbility::Random,
+            1 => StaticAbility::First,
+            2 => StaticAbility::Second,
+            3 => StaticAbility::Hidden,
+            ability => return Err(format!("unknown ability slot {}", ability)),
+        };
+        let item = match raw.item {
+            0 => None,
+            id => Some(Item::by_id(id).ok_or_else(|| format!("unknown item {}", id))?),
+        };
+
+        let ivs = [raw.iv_hp, raw.iv_attack, raw.iv_defense, raw.iv_sp_attack, raw.iv_sp_defense, raw.iv_speed];
+        let ivs = match ivs.iter().min() {
+            Some(&lowest) if lowest < 0 => StaticIvs::Random { perfect: (-1 - lowest) as u8 },
+            _ => StaticIvs::Fixed(Stats {
+                hp: raw.iv_hp as u64,
+                attack: raw.iv_attack as u64,
+                defense: raw.iv_defense as u64,
+                sp_attack: raw.iv_sp_attack as u64,
+                sp_defense: raw.iv_sp_defense as u64,
+                speed: raw.iv_speed as u64,
+            }),
+        };
+
+        let mut moves = Vec::new();
+        for &id in [raw.move0, raw.move1, raw.move2, raw.move3].iter().filter(|&&id| id != 0) {
+            let mv = POKEDEX.move_by_id(id).ok_or_else(|| format!("unknown move {}", id))?;
+            moves.push(mv.clone());
+        }
+
+        Ok(StaticEncounter {
+            national_dex: raw.species,
+            form: raw.form,
+            level: raw.level,
+            nature,
+            gender,
+            ability,
+            ivs,
+            evs: Stats {
+                hp: raw.ev_hp,
+                attack: raw.ev_attack,
+                defense: raw.ev_defense,
+                sp_attack: raw.ev_sp_attack,
+                sp_defense: raw.ev_sp_defense,
+                speed: raw.ev_speed,
+            },
+            dynamax_level: raw.dynamax_level,
+            item,
+            moves,
+            shiny_locked: raw.shiny_lock == 2,
+            catchable,
+        })
+    }
+}
+
+/// Every static encounter with the species in the given version that can be caught.
+pub fn static_encounters(version: GameVersion, species: &Species) -> Vec<&'static StaticEncounter> {
+    STATIC_ENCOUNTERS
+        .iter()
+        .filter(|encounter| encounter.catchable && encounter.is_in(version) && encounter.species() == Some(species))
+        .collect()
+}
+
+/// A way to obtain a species in game.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Source {
+    Wild(WildEncounter<'static>),
+    Static(&'static StaticEncounter),
+    Raid(RaidDen<'static>),
+    /// Evolve the species **from**, which can itself be obtained.
+    Evolution {
+        from: &'static Species,
+        evolution: &'static Evolution,
+    },
+}
+
+/// Every way to obtain the species in the given version.
+///
+/// A species can be obtained by evolution only if its pre-evolution can be obtained. Static
+/// encounters that can not be caught are left out.
+///
+/// The static encounter data does not say which version each encounter is in, so static
+/// encounters are reported for both versions, except for Zacian and Zamazenta. See
+/// `StaticEncounter::is_in`.
+pub fn sources(version: GameVersion, species: &Species) -> Vec<Source> {
+    let mut found: Vec<Source> = wild_encounters(version, species)
+        .into_iter()
+        .map(Source::Wild)
+        .collect();
+    found.extend(static_encounters(version, species).into_iter().map(Source::Static));
+    found.extend(raid_dens(version, species).into_iter().map(Source::Raid));
+
+    if let Some(from) = POKEDEX.pre_evolution(species.name()) {
+        if !sources(version, from).is_empty() {
+            let evolutions = from.evolutions.iter().filter(|evo| evo.into == species.name());
+            found.extend(evolutions.map(|evolution| Source::Evolution { from, evolution }));
+        }
+    }
+
+    found
+}
+
+/// Parses one encounter file. Weather tables use the given **kind**.
+///
+/// Each location starts with a line like "002 - Rolling Fields:", followed by tables like
+/// "\tOvercast (Lv. 7-10):", each with slots like "\t\t- Oddish      \t40%".
+fn parse_locations(s: &str, kind: EncounterKind) -> Result<Vec<Location>, DataError> {
+    let mut locations: Vec<Location> = Vec::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim_end();
+        let error = |message: &str| DataError::new(i + 1, line, message);
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(slot) = line.strip_prefix("\t\t- ") {
+            let table = locations
+                .last_mut()
+                .and_then(|location| location.tables.last_mut())
+                .ok_or_else(|| error("encounter slot outside of a table"))?;
+            let mut parts = slot.split('\t');
+            let species = parts.next().map(|name| form_name(name.trim()));
+            let percent = parts
+                .next()
+                .and_then(|percent| percent.trim().strip_suffix('%'))
+                .and_then(|percent| percent.parse::<u8>().ok());
+            match (species, percent) {
+                (Some(species), Some(percent)) => table.slots.push(EncounterSlot {
+                    species,
+                    percent,
+                }),
+                _ => return Err(error("malformed encounter slot")),
+            }
+        } else if let Some(header) = line.strip_prefix('\t') {
+            let location = locations
+                .last_mut()
+                .ok_or_else(|| error("encounter table outside of a location"))?;
+            let table = parse_table_header(header, kind)
+                .ok_or_else(|| error("malformed encounter table header"))?;
+            location.tables.push(table);
+        } else {
+            let mut parts = line.trim_end_matches(':').splitn(2, " - ");
+            let id = parts.next().and_then(|id| id.parse::<u32>().ok());
+            match (id, parts.next()) {
+                (Some(id), Some(name)) => locations.push(Location {
+                    id,
+                    name: name.split_whitespace().collect::<Vec<_>>().join(" "),
+                    tables: Vec::new(),
+                }),
+                _ => return Err(error("malformed location")),
+            }
+        }
+    }
+
+    Ok(locations)
+}
+
+/// Parses a line like "Overcast (Lv. 7-10):" into an empty table.
+fn parse_table_header(header: &str, kind: EncounterKind) -> Option<EncounterTable> {
+    let mut parts = header.trim_end_matches(':').splitn(2, " (Lv. ");
+    let condition = parts.next()?;
+    let mut levels = parts.next()?.trim_end_matches(')').splitn(2, '-');
+    let min_level = levels.next()?.parse::<u8>().ok()?;
+    let max_level = levels.next()?.parse::<u8>().ok()?;
+
+    let (kind, weather) = match condition {
+        "Fishing" => (EncounterKind::Fishing, Weather::All),
+        "Shaking Trees" => (EncounterKind::ShakingTrees, Weather::All),
+        weather => (kind, parse_weather(weather)?),
+    };
+
+    Some(EncounterTable {
+        kind,
+        weather,
+        min_level,
+        max_level,
+        slots: Vec::new(),
+    })
+}
+
+fn parse_weather(s: &str) -> Option<Weather> {
+    use Weather::*;
+    match s {
+        "All Weather" => Some(All),
+        "Normal Weather" => Some(Normal),
+        "Overcast" => Some(Overcast),
+        "Raining" => Some(Raining),
+        "Thunderstorm" => Some(Thunderstorm),
+        "Intense Sun" => Some(IntenseSun),
+        "Snowing" => Some(Snowing),
+        "Snowstorm" => Some(Snowstorm),
+        "Sandstorm" => Some(Sandstorm),
+        "Heavy Fog" => Some(HeavyFog),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn load_locations_test() {
+        for version in [GameVersion::Sword, GameVersion::Shield].iter() {
+            let locations = safe_load_locations(*version).expect("Could not load locations");
+            assert!(!locations.is_empty());
+            for location in locations.iter() {
+                for table in location.tables.iter() {
+                    assert!(table.min_level <= table.max_level);
+                    for slot in table.slots.iter() {
+                        assert!(slot.species().is_some(), "unknown species {} in {}", slot.species, location.name);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_locations_test() {
+        let text = "002 - Rolling Fields:\r\n\tOvercast (Lv. 7-10):\r\n\t\t- Oddish      \t40%\r\n\t\t- Zigzagoon-1 \t02%\r\n\t\t\r\n\tFishing (Lv. 4-6):\r\n\t\t- Magikarp    \t80%";
+        let locations = parse_locations(text, EncounterKind::Hidden).unwrap();
+        let expected = Location {
+            id: 2,
+            name: "Rolling Fields".to_string(),
+            tables: vec![
+                EncounterTable {
+                    kind: EncounterKind::Hidden,
+                    weather: Weather::Overcast,
+                    min_level: 7,
+                    max_level: 10,
+                    slots: vec![
+                        EncounterSlot { species: "Oddish".to_string(), percent: 40 },
+                        EncounterSlot { species: "Zigzagoon 1".to_string(), percent: 2 },
+                    ],
+                },
+                EncounterTable {
+                    kind: EncounterKind::Fishing,
+                    weather: Weather::All,
+                    min_level: 4,
+                    max_level: 6,
+                    slots: vec![
+                        EncounterSlot { species: "Magikarp".to_string(), percent: 80 },
+                    ],
+                },
+            ],
+        };
+        assert_eq!(locations, vec![expected]);
+
+        let err = parse_locations("\t\t- Oddish \t40%", EncounterKind::Hidden).unwrap_err();
+        assert_eq!(err.line, 1);
+    }
+
+    #[test]
+    fn version_exclusive_test() {
+        let seedot = POKEDEX.species("Seedot").unwrap();
+        assert!(!wild_encounters(GameVersion::Sword, seedot).is_empty());
+        assert!(wild_encounters(GameVersion::Shield, seedot).is_empty());
+
+        let lotad = POKEDEX.species("Lotad").unwrap();
+        assert!(wild_encounters(GameVersion::Sword, lotad).is_empty());
+        let in_shield = wild_encounters(GameVersion::Shield, lotad);
+        assert!(in_shield.iter().any(|e| e.location.name == "West Lake Axewell"
+            && e.table.kind == EncounterKind::Overworld
+            && e.table.weather == Weather::Overcast
+            && e.slot.percent == 60));
+    }
+
+    #[test]
+    fn weather_test() {
+        let dreepy = POKEDEX.species("Dreepy").unwrap();
+        let mut weathers: Vec<_> = wild_encounters(GameVersion::Sword, dreepy)
+            .iter()
+            .map(|e| e.table.weather)
+            .collect();
+        weathers.sort();
+        assert_eq!(weathers, vec![Weather::Overcast, Weather::Thunderstorm, Weather::HeavyFog]);
+    }
+
+    #[test]
+    fn item_holders_test() {
+        let leftovers = Item::by_name("Leftovers").unwrap();
+        let holders = wild_item_holders(GameVersion::Sword, leftovers);
+        assert!(!holders.is_empty());
+        for holder in holders.iter() {
+            let species = holder.encounter.slot.species().unwrap();
+            assert!(species.wild_held_items().iter().any(|held| held.item == leftovers));
+        }
+
+        let munchlax = holders
+            .iter()
+            .find(|holder| holder.encounter.slot.species == "Munchlax")
+            .expect("no wild Munchlax");
+        assert_eq!(munchlax.chance, 55);
+        assert!(!munchlax.encounter.location.item_holders(leftovers).is_empty());
+    }
+
+    #[test]
+    fn static_encounters_test() {
+        let encounters = safe_load_static_encounters().expect("Could not load static encounters");
+        for encounter in encounters.iter() {
+            assert!(encounter.species().is_some(), "unknown species {} form {}", encounter.national_dex, encounter.form);
+        }
+        // every row is kept, including the battles that can not end in a catch
+        assert_eq!(encounters.len(), 257);
+
+        let zacian = POKEDEX.species("Zacian").unwrap();
+        assert!(!static_encounters(GameVersion::Sword, zacian).is_empty());
+        assert!(static_encounters(GameVersion::Shield, zacian).is_empty());
+    }
+
+    #[test]
+    fn catchable_static_encounters_test() {
+        let find = |dex: u32, form: u32, level: u8| {
+            STATIC_ENCOUNTERS
+                .iter()
+                .filter(move |encounter| encounter.national_dex == dex && encounter.form == form && encounter.level == level)
+        };
+        // Eternamax Eternatus is only fought, the regular form is caught
+        assert_eq!(find(890, 1, 60).count(), 2);
+        assert!(find(890, 1, 60).all(|encounter| !encounter.catchable));
+        assert!(find(890, 0, 60).all(|encounter| encounter.catchable));
+        // Zacian is only caught at level 70, and not in the battle after the story
+        assert!(find(888, 0, 1).chain(find(888, 0, 55)).all(|encounter| !encounter.catchable));
+        assert_eq!(find(888, 0, 70).filter(|encounter| encounter.catchable).count(), 1);
+        // the rampaging Gyarados after the story
+        assert!(find(130, 0, 62).all(|encounter| !encounter.catchable));
+        assert!(find(37, 0, 24).all(|encounter| encounter.catchable));
+
+        let zacian = POKEDEX.species("Zacian").unwrap();
+        let levels: Vec<_> = static_encounters(GameVersion::Sword, zacian).iter().map(|encounter| encounter.level).collect();
+        assert_eq!(levels, vec![70]);
+    }
+
+    #[test]
+    fn decode_static_encounter_test() {
+        let zacian = POKEDEX.species("Zacian").unwrap();
+        let encounters = static_encounters(GameVersion::Sword, zacian);
+        assert!(encounters.iter().all(|encounter| encounter.shiny_locked));
+
+        let catchable = encounters
+            .iter()
+            .find(|encounter| encounter.level == 70 && encounter.ivs == StaticIvs::Random { perfect: 3 })
+            .expect("missing the catchable Zacian");
+        assert_eq!(catchable.nature, None);
+        assert_eq!(catchable.ability_name(), Some("Intrepid Sword"));
+        let moves: Vec<_> = catchable.moves.iter().map(|mv| mv.name()).collect();
+        assert_eq!(moves.len(), 4);
+        assert!(moves.contains(&"Iron Head"));
+
+        let pokemon = catchable.to_pokemon().unwrap();
+        assert_eq!(pokemon.species_name(), "Zacian");
+        assert_eq!(pokemon.level(), 70);
+        assert_eq!(pokemon.ability(), "Intrepid Sword");
+        assert_eq!(pokemon.moves().len(), 4);
+
+        // the static Hatterene is always female
+        let hatterene = STATIC_ENCOUNTERS.iter().find(|encounter| encounter.national_dex == 858).unwrap();
+        assert_eq!(hatterene.gender, Some(Gender::Female));
+        assert_eq!(hatterene.nature, None);
+        assert_eq!(hatterene.to_pokemon().unwrap().gender(), Some(Gender::Female));
+    }
+
+    #[test]
+    fn sources_test() {
+        let dragapult = POKEDEX.species("Dragapult").unwrap();
+        let found = sources(GameVersion::Sword, dragapult);
+        assert!(found.iter().any(|source| match source {
+            Source::Evolution { from, .. } => from.name() == "Drakloak",
+            _ => false,
+        }));
+
+        // Sirfetch'd evolves from Galarian Farfetch'd, which is only found in Sword
+        let sirfetchd = POKEDEX.species("Sirfetch’d").unwrap();
+        assert!(!sources(GameVersion::Shield, sirfetchd)
+            .iter()
+            .any(|source| matches!(source, Source::Evolution { .. })));
+    }
+}
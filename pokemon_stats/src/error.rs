@@ -0,0 +1,157 @@
+use serde::de::DeserializeOwned;
+use std::fmt;
+
+/// A problem found while reading a pokemon or team in the Pokemon Showdown format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line the problem is on, starting from 1.
+    pub line: usize,
+    /// The text of the offending line.
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new<S: Into<String>>(line: usize, text: S, kind: ParseErrorKind) -> Self {
+        ParseError {
+            line,
+            text: text.into(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {} (in \"{}\")", self.line, self.kind, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A problem in one of the data files embedded in the crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataError {
+    /// The line the problem is on, starting from 1.
+    pub line: usize,
+    /// The text of the offending line.
+    pub text: String,
+    pub message: String,
+}
+
+impl DataError {
+    pub fn new<S: Into<String>, M: Into<String>>(line: usize, text: S, message: M) -> Self {
+        DataError {
+            line,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {} (in \"{}\")", self.line, self.message, self.text)
+    }
+}
+
+impl std::error::Error for DataError {}
+
+/// Reads every row of an embedded tab separated file with a header line into **R**, then
+/// converts it with **convert**, returning the first problem found with its line.
+pub(crate) fn load_tsv<R, T, F>(tsv: &[u8], convert: F) -> Result<Vec<T>, DataError>
+where
+    R: DeserializeOwned,
+    F: Fn(R) -> Result<T, String>,
+{
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b'\t')
+        .from_reader(tsv);
+    let headers = reader
+        .headers()
+        .map_err(|err| DataError::new(1, "", err.to_string()))?
+        .clone();
+
+    let mut rows = Vec::new();
+    for (i, result) in reader.records().enumerate() {
+        // the header is line 1
+        let line = i + 2;
+        let record = result.map_err(|err| DataError::new(line, "", err.to_string()))?;
+        let text = record.iter().collect::<Vec<_>>().join("\t");
+        let raw: R = record
+            .deserialize(Some(&headers))
+            .map_err(|err| DataError::new(line, text.as_str(), err.to_string()))?;
+        let row = convert(raw).map_err(|message| DataError::new(line, text.as_str(), message))?;
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// The reason a line could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// There was no text to parse.
+    Empty,
+    /// The first line should look like "Nickname (Species) @ Item".
+    MalformedNameLine,
+    UnknownSpecies(String),
+    UnknownMove(String),
+    /// A move that can not be used in Sword and Shield, like Hidden Power.
+    UnusableMove(String),
+    UnknownItem(String),
+    UnknownAbility(String),
+    /// An ability the species can not have.
+    IllegalAbility(String),
+    UnknownNature(String),
+    /// A stat label other than HP, Atk, Def, SpA, SpD or Spe.
+    UnknownStat(String),
+    /// An EV or IV entry that is not like "252 Atk".
+    MalformedStats,
+    /// A single EV above 252 or IV above 31.
+    StatOutOfRange(u64),
+    /// The EVs add up to more than 510.
+    EvTotalOver510(u64),
+    /// A level outside of 1 to 100.
+    InvalidLevel(String),
+    /// Happiness outside of 0 to 255.
+    InvalidHappiness(String),
+    /// A dynamax level outside of 0 to 10.
+    InvalidDynamaxLevel(String),
+    /// A flag, like Shiny, that is neither "Yes" nor "No".
+    InvalidYesNo(String),
+    /// A line that is not part of the format, like a misspelled "Abilty: Levitate".
+    UnrecognizedLine,
+    /// A pokemon can know at most 4 moves.
+    TooManyMoves,
+    /// A team can have at most 6 pokemon.
+    TooManyMembers,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ParseErrorKind::*;
+        match self {
+            Empty => write!(f, "nothing to parse"),
+            MalformedNameLine => write!(f, "could not read the name line"),
+            UnknownSpecies(name) => write!(f, "unknown species \"{}\"", name),
+            UnknownMove(name) => write!(f, "unknown move \"{}\"", name),
+            UnusableMove(name) => write!(f, "move \"{}\" can not be used in Sword and Shield", name),
+            UnknownItem(name) => write!(f, "unknown item \"{}\"", name),
+            UnknownAbility(name) => write!(f, "unknown ability \"{}\"", name),
+            IllegalAbility(name) => write!(f, "the species can not have the ability \"{}\"", name),
+            UnknownNature(name) => write!(f, "unknown nature \"{}\"", name),
+            UnknownStat(label) => write!(f, "unknown stat \"{}\"", label),
+            MalformedStats => write!(f, "malformed stats"),
+            StatOutOfRange(value) => write!(f, "stat value {} is out of range", value),
+            EvTotalOver510(total) => write!(f, "EVs total {}, more than 510", total),
+            InvalidLevel(level) => write!(f, "invalid level \"{}\"", level),
+            InvalidHappiness(value) => write!(f, "invalid happiness \"{}\"", value),
+            InvalidDynamaxLevel(value) => write!(f, "invalid dynamax level \"{}\"", value),
+            InvalidYesNo(value) => write!(f, "expected Yes or No, found \"{}\"", value),
+            UnrecognizedLine => write!(f, "unrecognized line"),
+            TooManyMoves => write!(f, "more than 4 moves"),
+            TooManyMembers => write!(f, "more than 6 pokemon"),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::pokedex::POKEDEX;
+use crate::pokemon::{
+    form_name,
+    PureType,
+    Species,
+};
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+/// One way a species can evolve, into the species named `into`.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "RawEvolution")]
+pub struct Evolution {
+    /// The name of the species evolved into, as used by `Species::name`.
+    pub into: String,
+    pub method: EvolutionMethod,
+}
+
+impl Evolution {
+    /// The species evolved into.
+    pub fn species(&self) -> Option<&'static Species> {
+        POKEDEX.species(&self.into)
+    }
+}
+
+/// How a species evolves.
+///
+/// Items, moves and species are given by their numeric index in game.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EvolutionMethod {
+    /// Reach the given level.
+    LevelUp(u32),
+    LevelUpFriendship,
+    LevelUpFriendshipMorning,
+    LevelUpFriendshipNight,
+    /// Level up with high affection while knowing a move of the given type.
+    LevelUpAffectionMoveType(PureType),
+    /// Level up at night while holding the item.
+    LevelUpHeldItemNight(u32),
+    /// Level up while knowing the move.
+    LevelUpKnowMove(u32),
+    /// Level up when Attack and Defense are equal.
+    LevelUpAttackEqualsDefense,
+    /// Level up when Attack is higher than Defense.
+    LevelUpAttackGreater,
+    /// Level up when Defense is higher than Attack.
+    LevelUpDefenseGreater,
+    LevelUpNight,
+    LevelUpMale,
+    LevelUpFemale,
+    /// Level up as a female, evolving into the alternate form.
+    LevelUpFormFemale,
+    /// Level up with a free space in the party, creating Shedinja along the way.
+    LevelUpNinjask,
+    /// Level up with at least the given beauty condition.
+    LevelUpBeauty(u32),
+    /// Level up with the given species in the party.
+    LevelUpWithTeammate(u32),
+    /// Level up with a Dark type in the party.
+    LevelUpDarkTeammate,
+    /// Level up while the system is held upside down.
+    LevelUpInverted,
+    /// Level up while it is raining in the overworld.
+    LevelUpWeather,
+    /// Level up in the given game version.
+    LevelUpVersion(u32),
+    /// Level up with a nature that gives the Amped form.
+    LevelUpAmped,
+    /// Level up with a nature that gives the Low Key form.
+    LevelUpLowKey,
+    UseItem(u32),
+    UseItemMale(u32),
+    UseItemFemale(u32),
+    Trade,
+    /// Trade while holding the item.
+    TradeHeldItem(u32),
+    /// Trade for a specific species.
+    TradeSpecies,
+    /// Land the given number of critical hits in one battle.
+    CriticalHits(u32),
+    /// Take at least the given amount of damage, then walk under the stone bridge in Dusty Bowl.
+    DamageTaken(u32),
+    /// Spin around while holding a sweet.
+    Spin,
+}
+
+/// The evolution as it is stored in the data, before being converted to an `Evolution`.
+#[derive(Deserialize)]
+struct RawEvolution {
+    /// Species and form, like "Raichu-1".
+    species: String,
+    method: String,
+    method_value: serde_json::Value,
+}
+
+impl TryFrom<RawEvolution> for Evolution {
+    type Error = String;
+
+    fn try_from(raw: RawEvolution) -> Result<Self, Self::Error> {
+        use EvolutionMethod::*;
+
+        let value = match &raw.method_value {
+            serde_json::Value::Number(n) => n.as_u64(),
+            serde_json::Value::String(s) => s.parse::<u64>().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| format!("invalid evolution method value: {}", raw.method_value))? as u32;
+
+        let method = match raw.method.as_str() {
+            "LevelUp" => LevelUp(value),
+            "LevelUpFriendship" => LevelUpFriendship,
+            "LevelUpFriendshipMorning" => LevelUpFriendshipMorning,
+            "LevelUpFriendshipNight" => LevelUpFriendshipNight,
+            "LevelUpAffection50MoveType" => {
+                let ty = PureType::from_game_index(value as u8)
+                    .ok_or_else(|| format!("invalid type for evolution: {}", value))?;
+                LevelUpAffectionMoveType(ty)
+            }
+            "LevelUpHeldItemNight" => LevelUpHeldItemNight(value),
+            "LevelUpKnowMove" => LevelUpKnowMove(value),
+            "LevelUpAeqD" => LevelUpAttackEqualsDefense,
+            "LevelUpATK" => LevelUpAttackGreater,
+            "LevelUpDEF" => LevelUpDefenseGreater,
+            "LevelUpNight" => LevelUpNight,
+            "LevelUpMale" => LevelUpMale,
+            "LevelUpFemale" => LevelUpFemale,
+            "LevelUpFormFemale1" => LevelUpFormFemale,
+            "LevelUpNinjask" => LevelUpNinjask,
+            "LevelUpBeauty" => LevelUpBeauty(value),
+            "LevelUpWithTeammate" => LevelUpWithTeammate(value),
+            "LevelUpMoveType" => LevelUpDarkTeammate,
+            "LevelUpInverted" => LevelUpInverted,
+            "LevelUpWeather" => LevelUpWeather,
+            "LevelUpVersion" => LevelUpVersion(value),
+            "U46" => LevelUpAmped,
+            "U47" => LevelUpLowKey,
+            "UseItem" => UseItem(value),
+            "UseItemMale" => UseItemMale(value),
+            "UseItemFemale" => UseItemFemale(value),
+            "Trade" => Trade,
+            "TradeHeldItem" => TradeHeldItem(value),
+            "TradeSpecies" => TradeSpecies,
+            "U43" => CriticalHits(value),
+            "U44" => DamageTaken(value),
+            "U45" => Spin,
+            method => return Err(format!("unknown evolution method: {}", method)),
+        };
+
+        Ok(Evolution {
+            into: form_name(&raw.species),
+            method,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::pokemon::POKEMON_VEC;
+
+    #[test]
+    fn all_evolutions_exist_test() {
+        for species in POKEMON_VEC.iter() {
+            for evolution in species.evolutions() {
+                assert!(
+                    evolution.species().is_some(),
+                    "{} evolves into unknown species {}",
+                    species.name(),
+                    evolution.into,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use crate::evolution::EvolutionMethod;
+use crate::pokemon::POKEMON_VEC;
+use lazy_static::lazy_static;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+const ITEM_FLAVOR_TSV: &str = include_str!("../../data/raw/sword_shield_item_flavor.tsv");
+
+/// Item names used in game and by Pokemon Showdown that are spelled differently in the data,
+/// with the spelling in the data.
+const NAME_ALIASES: &[(&str, &str)] = &[("Up-Grade", "Upgrade")];
+
+/// Loads every item in the games. Unused item ids, named "???" in the data, are skipped.
+pub fn load_items() -> Vec<Item> {
+    let evolution_items = evolution_item_ids();
+    ITEM_FLAVOR_TSV
+        .lines()
+        .enumerate()
+        .skip(1)
+        .map(|(id, line)| {
+            let (name, description) = split_item_line(line.trim_end());
+            (id as u32, name, description)
+        })
+        .filter(|&(_, name, _)| name != "???")
+        .map(|(id, name, description)| Item {
+            id,
+            name: name.to_string(),
+            description: description.to_string(),
+            category: ItemCategory::classify(name, description, evolution_items.contains(&id)),
+        })
+        .collect()
+}
+
+lazy_static! {
+    pub static ref ITEMS: Vec<Item> = load_items();
+    static ref ITEMS_BY_ID: HashMap<u32, usize> = ITEMS
+        .iter()
+        .enumerate()
+        .map(|(i, item)| (item.id, i))
+        .collect();
+    static ref ITEMS_BY_NAME: HashMap<&'static str, usize> = {
+        let mut by_name = HashMap::new();
+        for (i, item) in ITEMS.iter().enumerate() {
+            by_name.entry(item.name.as_str()).or_insert(i);
+        }
+        by_name
+    };
+}
+
+/// Something that can be carried in the bag or held by a pokemon.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Item {
+    /// The numeric id of the item in game.
+    pub id: u32,
+    pub name: String,
+    /// The description shown in the bag.
+    pub description: String,
+    pub category: ItemCategory,
+}
+
+impl Item {
+    /// Finds the item with the numeric id used in game, like 640 for Assault Vest.
+    pub fn by_id(id: u32) -> Option<&'static Item> {
+        ITEMS_BY_ID.get(&id).map(|&i| &ITEMS[i])
+    }
+
+    /// Finds an item by name, like "Assault Vest". Straight apostrophes, like in "King's Rock",
+    /// match the curly ones used in game, and names spelled differently in the data, like
+    /// Up-Grade, are found by their in game name too.
+    pub fn by_name(name: &str) -> Option<&'static Item> {
+        let name = NAME_ALIASES
+            .iter()
+            .find(|&&(alias, _)| alias == name)
+            .map_or(name, |&(_, data_name)| data_name);
+        let name = name.replace('\'', "’");
+        ITEMS_BY_NAME.get(name.as_str()).map(|&i| &ITEMS[i])
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+}
+
+/// An item a wild pokemon can be found holding, for example by stealing it with Thief.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WildHeldItem {
+    pub item: &'static Item,
+    /// The chance of a wild pokemon holding the item, out of 100.
+    pub chance: u8,
+}
+
+/// A rough grouping of items by what they are used for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ItemCategory {
+    PokeBall,
+    Berry,
+    /// An item that has an effect in battle when held, like Life Orb.
+    HeldBattle,
+    /// A TM, TR or HM.
+    Machine,
+    /// An item used to evolve a species. This includes items that also work in battle, like
+    /// Metal Coat.
+    Evolution,
+    /// An item meant to be sold, like Nugget.
+    Treasure,
+    Other,
+}
+
+/// Held battle items whose descriptions do not say they are held.
+const UNMARKED_HELD_ITEMS: &[&str] = &["Throat Spray", "Heavy-Duty Boots", "Blunder Policy"];
+
+impl ItemCategory {
+    fn classify(name: &str, description: &str, is_evolution_item: bool) -> ItemCategory {
+        let is_machine = name.len() == 4
+            && (name.starts_with("TM") || name.starts_with("TR") || name.starts_with("HM"))
+            && name[2..].chars().all(|c| c.is_ascii_digit());
+
+        if is_machine {
+            ItemCategory::Machine
+        } else if name.ends_with(" Berry") {
+            ItemCategory::Berry
+        } else if name == "Poké Ball" || (name.ends_with(" Ball") && description.contains("Poké Ball")) {
+            ItemCategory::PokeBall
+        } else if is_evolution_item {
+            ItemCategory::Evolution
+        } else if description.contains("held by a Pokémon") || UNMARKED_HELD_ITEMS.contains(&name) {
+            ItemCategory::HeldBattle
+        } else if description.contains("can be sold") {
+            ItemCategory::Treasure
+        } else {
+            ItemCategory::Other
+        }
+    }
+}
+
+/// The ids of the items used in evolutions.
+fn evolution_item_ids() -> HashSet<u32> {
+    use EvolutionMethod::*;
+    POKEMON_VEC
+        .iter()
+        .flat_map(|species| species.evolutions().iter())
+        .filter_map(|evolution| match evolution.method {
+            UseItem(id) | UseItemMale(id) | UseItemFemale(id) | TradeHeldItem(id) | LevelUpHeldItemNight(id) => Some(id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The name of the item with the numeric id used in game, like 640 for "Assault Vest".
+/// Id 0 is no item, and unused ids have no name.
+pub fn item_name(id: u32) -> Option<&'static str> {
+    Item::by_id(id).map(|item| item.name())
+}
+
+/// Words that start a description but never follow an item name.
+const DESCRIPTION_OPENERS: &[&str] = &[
+    "A", "An", "The", "It", "Its", "This", "These", "One", "If", "When", "Used", "Raises",
+    "Stationery", "-",
+];
+
+/// Splits a line like "Master Ball The best Poké Ball..." into the item name and description.
+///
+/// The file separates the two with a space, like every other word. The description starts at
+/// the first opening word, like "An", or at the first capitalized word followed by a lower case
+/// word, like "Salt that...". A possessive place or person, like "Lavaridge Town’s" or
+/// "Professor Sonia’s", is part of the description.
+fn split_item_line(line: &str) -> (&str, &str) {
+    let words: Vec<&str> = line.split(' ').collect();
+    let is_capitalized = |word: &str| word.starts_with(char::is_uppercase);
+    let is_lowercase = |word: &str| word.starts_with(char::is_lowercase);
+
+    let mut start = None;
+    for i in 1..words.len() {
+        let word = words[i];
+        if DESCRIPTION_OPENERS.contains(&word) {
+            start = Some(i);
+            break;
+        }
+        if is_capitalized(word) && matches!(words.get(i + 1), Some(next) if is_lowercase(next)) {
+            let possessive = word.ends_with("’s")
+                && (word.starts_with("Town") || word.starts_with("City") || words[i - 1] == "Professor");
+            start = Some(if possessive && i > 1 { i - 1 } else { i });
+            break;
+        }
+    }
+
+    // every line has a name, even when the description can not be told apart
+    let name_words = start.unwrap_or(1);
+    let name_len = words[..name_words].iter().map(|word| word.len() + 1).sum::<usize>() - 1;
+    (&line[..name_len], line[name_len..].trim_start())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_item_line_test() {
+        assert_eq!(split_item_line("None ???"), ("None", "???"));
+        assert_eq!(split_item_line("??? - - -"), ("???", "- - -"));
+        assert_eq!(split_item_line("Fresh Water Water with high mineral content."), ("Fresh Water", "Water with high mineral content."));
+        assert_eq!(split_item_line("Key to Room 1 A key that opens a door."), ("Key to Room 1", "A key that opens a door."));
+        assert_eq!(split_item_line("Lava Cookie Lavaridge Town’s local specialty."), ("Lava Cookie", "Lavaridge Town’s local specialty."));
+        assert_eq!(split_item_line("Light Stone Reshiram’s body was destroyed."), ("Light Stone", "Reshiram’s body was destroyed."));
+        assert_eq!(split_item_line("Throat Spray Raises Sp. Atk when a Pokémon uses a sound-based move."), ("Throat Spray", "Raises Sp. Atk when a Pokémon uses a sound-based move."));
+    }
+
+    #[test]
+    fn load_items_test() {
+        let items = load_items();
+        assert!(items.iter().all(|item| item.name != "???" && item.id != 0));
+        for item in items.iter() {
+            assert!(!item.description.is_empty(), "{} has no description", item.name);
+        }
+    }
+
+    #[test]
+    fn item_lookup_test() {
+        assert_eq!(item_name(0), None);
+        assert_eq!(item_name(1), Some("Master Ball"));
+        assert_eq!(item_name(639), Some("Weakness Policy"));
+        assert_eq!(item_name(640), Some("Assault Vest"));
+        assert_eq!(item_name(287), Some("Choice Scarf"));
+        assert_eq!(item_name(1101), None);
+
+        let vest = Item::by_name("Assault Vest").expect("missing Assault Vest");
+        assert_eq!(vest.id, 640);
+        assert!(vest.description().starts_with("An item to be held by a Pokémon."));
+        assert_eq!(Item::by_name("King's Rock").map(|item| item.id), Some(221));
+        assert!(Item::by_name("Assault Vestt").is_none());
+        assert!(Item::by_name("???").is_none());
+        assert_eq!(Item::by_name("Up-Grade"), Item::by_name("Upgrade"));
+        assert!(Item::by_name("Up-Grade").is_some());
+    }
+
+    #[test]
+    fn item_category_test() {
+        let category = |name| Item::by_name(name).unwrap().category;
+        assert_eq!(category("Ultra Ball"), ItemCategory::PokeBall);
+        assert_eq!(category("Poké Ball"), ItemCategory::PokeBall);
+        assert_eq!(category("Sitrus Berry"), ItemCategory::Berry);
+        assert_eq!(category("Life Orb"), ItemCategory::HeldBattle);
+        assert_eq!(category("Heavy-Duty Boots"), ItemCategory::HeldBattle);
+        assert_eq!(category("Eviolite"), ItemCategory::HeldBattle);
+        assert_eq!(category("TR56"), ItemCategory::Machine);
+        assert_eq!(category("Thunder Stone"), ItemCategory::Evolution);
+        assert_eq!(category("Metal Coat"), ItemCategory::Evolution);
+        assert_eq!(category("Big Nugget"), ItemCategory::Treasure);
+        assert_eq!(category("Potion"), ItemCategory::Other);
+    }
+}
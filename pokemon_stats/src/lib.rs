@@ -1,8 +1,21 @@
+pub mod abilities;
+pub mod battle_tower;
+pub mod damage;
+pub mod encounters;
+pub mod error;
+pub mod evolution;
+pub mod items;
 pub mod moves;
+pub mod nature;
 pub mod party;
+pub mod pokedex;
 pub mod pokemon;
+pub mod raids;
+pub mod trainers;
 
+pub use error::*;
 pub use moves::*;
+pub use pokedex::*;
 pub use pokemon::{
     *,
     PureType::*,
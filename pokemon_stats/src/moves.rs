@@ -1,29 +1,81 @@
+use crate::encounters::GameVersion;
+use crate::pokedex::POKEDEX;
+use crate::raids::{
+    tr_sources,
+    TrSource,
+};
 use crate::pokemon::{
+    Efficacy,
+    PokemonType,
     PureType,
+    Species,
     Stat,
 };
 use enumset::EnumSet;
 use lazy_static::lazy_static;
 use serde::Deserialize;
 use serde_repr::Deserialize_repr;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 use std::convert::From;
 
 const MOVES_TSV: &[u8] = include_bytes!("../../data/raw/sword_shield_move_info.tsv");
+const MOVE_DESCRIPTIONS_TSV: &[u8] = include_bytes!("../../data/raw/sword_shield_move_descriptions.tsv");
+const SNAPPED_MOVES_TSV: &[u8] = include_bytes!("../../data/raw/sword_shield_snapped_moves.tsv");
 
 /// Loads a list of moves as a vector. Returns the parsing error.
 pub fn safe_load_moves() -> Result<Vec<Move>, csv::Error> {
+    let descriptions = safe_load_move_descriptions()?;
+    let snapped = safe_load_snapped_moves()?;
     let mut reader = csv::ReaderBuilder::new()
         .delimiter(b'\t')
         .from_reader(MOVES_TSV);
     let mut all_moves = Vec::with_capacity(100);
     for result in reader.deserialize() {
-        let mv: Move = result?;
+        let mut mv: Move = result?;
+        if let Some(description) = descriptions.get(mv.name()) {
+            mv.description = description.clone();
+        }
+        mv.snapped = snapped.contains(&mv.move_id);
         all_moves.push(mv);
     }
 
     Ok(all_moves)
 }
 
+/// Loads the in game description of each move, keyed by the move name.
+fn safe_load_move_descriptions() -> Result<HashMap<String, String>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b'\t')
+        .has_headers(false)
+        .quoting(false)
+        .from_reader(MOVE_DESCRIPTIONS_TSV);
+    let mut descriptions = HashMap::new();
+    for result in reader.deserialize() {
+        let (name, description): (String, String) = result?;
+        descriptions.insert(name, description);
+    }
+
+    Ok(descriptions)
+}
+
+/// Loads the numeric ids of the moves removed from Sword and Shield.
+fn safe_load_snapped_moves() -> Result<HashSet<u32>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b'\t')
+        .has_headers(false)
+        .from_reader(SNAPPED_MOVES_TSV);
+    let mut snapped = HashSet::new();
+    for result in reader.deserialize() {
+        let (id, _name): (u32, String) = result?;
+        snapped.insert(id);
+    }
+
+    Ok(snapped)
+}
+
 /// Loads the list of moves as a vector.
 #[deprecated(
     since = "0.2.0",
@@ -69,6 +121,11 @@ impl MoveId {
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
+
+    /// Looks up the full data for this move in `MOVE_VEC`.
+    pub fn resolve(&self) -> Option<&'static Move> {
+        POKEDEX.move_by_name(self.name())
+    }
 }
 
 /// Move Category, dictates the attack type
@@ -107,6 +164,9 @@ pub enum Target {
 pub struct Move {
     #[serde(rename="Moves")]
     pub id: MoveId,
+    /// The numeric id of the move in game.
+    #[serde(rename="MoveID")]
+    pub move_id: u32,
     #[serde(rename="CanUseMove", deserialize_with = "deserialize::tf")]
     pub available_in_gen8: bool,
     #[serde(rename="Type", deserialize_with = "deserialize::de_type")]
@@ -195,6 +255,12 @@ pub struct Move {
     pub healing: Option<i8>,
     #[serde(deserialize_with="deserialize::target")]
     pub target: Target,
+    /// The in game description, from a separate data file.
+    #[serde(skip)]
+    pub description: String,
+    /// If the move was removed from Sword and Shield, from a separate data file.
+    #[serde(skip)]
+    pub snapped: bool,
 }
 
 impl Move {
@@ -202,6 +268,44 @@ impl Move {
         self.id.name.as_str()
     }
 
+    /// The in game description of the move.
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    /// How effective the move is against the species. Unlike `PureType::against`, this
+    /// includes moves with special rules, like Freeze-Dry being super effective on Water types.
+    pub fn efficacy_against(&self, pokemon: &Species) -> Efficacy {
+        match pokemon.types {
+            PokemonType::Single(ty) => self.efficacy_against_type(ty),
+            PokemonType::Double(ty1, ty2) => self.efficacy_against_type(ty1) * self.efficacy_against_type(ty2),
+        }
+    }
+
+    /// How effective the move is against a single defending type.
+    fn efficacy_against_type(&self, defense: PureType) -> Efficacy {
+        match (self.name(), defense) {
+            ("Freeze-Dry", PureType::Water) => Efficacy::Pow2(1),
+            // Flying Press is both Fighting and Flying type
+            ("Flying Press", _) => {
+                PureType::efficacy(PureType::Fighting, defense) * PureType::efficacy(PureType::Flying, defense)
+            }
+            // Thousand Arrows hits Flying types as if they were not Flying
+            ("Thousand Arrows", PureType::Flying) => Efficacy::Pow2(0),
+            _ => PureType::efficacy(self.move_type, defense),
+        }
+    }
+
+    /// True for moves removed from Sword and Shield, like Hidden Power and Return.
+    pub fn is_snapped(&self) -> bool {
+        self.snapped
+    }
+
+    /// True if the move can be used in Sword and Shield.
+    pub fn is_usable(&self) -> bool {
+        self.available_in_gen8 && !self.snapped
+    }
+
     pub fn effect_on_stats(&self, stat: Stat) -> Option<(u8, u8)> {
         if self.stat1.contains(stat) {
             return Some((self.stat1_percent, self.stat1_stage));
@@ -331,29 +435,8 @@ mod deserialize {
             fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E>
                 where E: de::Error,
             {
-                use PureType::*;
-                match v {
-                    0 => Ok(Normal),
-                    1 => Ok(Fighting),
-                    2 => Ok(Flying),
-                    3 => Ok(Poison),
-                    4 => Ok(Ground),
-                    5 => Ok(Rock),
-                    6 => Ok(Bug),
-                    7 => Ok(Ghost),
-                    8 => Ok(Steel),
-                    9 => Ok(Fire),
-                    10 => Ok(Water),
-                    11 => Ok(Grass),
-                    12 => Ok(Electric),
-                    13 => Ok(Psychic),
-                    14 => Ok(Ice),
-                    15 => Ok(Dragon),
-                    16 => Ok(Dark),
-                    17 => Ok(Fairy),
-
-                    v => Err(E::invalid_value(de::Unexpected::Unsigned(v as u64), &self)),
-                }
+                PureType::from_game_index(v as u8)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Signed(v as i64), &self))
             }
         }
 
@@ -434,6 +517,11 @@ impl TM {
         }
     }
 
+    /// The number of the TM, like 56 for TM56.
+    pub fn id(self) -> u64 {
+        self.id
+    }
+
     pub fn as_move(self) -> MoveId {
         MoveId {
             name: TM_S[self.id as usize].to_string(),
@@ -455,11 +543,21 @@ impl TR {
         }
     }
 
+    /// The number of the TR, like 56 for TR56.
+    pub fn id(self) -> u64 {
+        self.id
+    }
+
     pub fn as_move(self) -> MoveId {
         MoveId {
             name: TR_S[self.id as usize].to_string(),
         }
     }
+
+    /// Every raid that drops this TR in the version, with the den star ratings and drop chances.
+    pub fn sources(self, version: GameVersion) -> Vec<TrSource> {
+        tr_sources(version, self)
+    }
 }
 
 pub const TM_S: &'static[&'static str] = &[
@@ -697,6 +795,80 @@ Index	Moves	Version	MoveID	CanUseMove	Type	Quality	Category	Power	Accuracy	PP	Pr
         assert_eq!(Some(50), recover.healing);
     }
 
+    #[test]
+    fn efficacy_against_test() {
+        let mv = |name| POKEDEX.move_by_name(name).unwrap();
+        let species = |name| POKEDEX.species(name).unwrap();
+
+        // Water/Ground is weak to Freeze-Dry, but immune to Thunderbolt
+        let seismitoad = species("Seismitoad");
+        assert_eq!(mv("Freeze-Dry").efficacy_against(seismitoad), Efficacy::Pow2(2));
+        assert_eq!(mv("Ice Beam").efficacy_against(seismitoad), Efficacy::Pow2(0));
+
+        // Grass is weak to Flying, and Ice is weak to Fighting
+        assert_eq!(mv("Flying Press").efficacy_against(species("Abomasnow")), Efficacy::Pow2(2));
+        assert_eq!(mv("Flying Press").efficacy_against(species("Gengar")), Efficacy::Zero);
+
+        let corviknight = species("Corviknight");
+        assert_eq!(mv("Thousand Arrows").efficacy_against(corviknight), Efficacy::Pow2(1));
+        assert_eq!(mv("Earthquake").efficacy_against(corviknight), Efficacy::Zero);
+    }
+
+    #[test]
+    fn snapped_moves_test() {
+        let snapped = safe_load_snapped_moves().expect("Could not load the snapped moves");
+        assert_eq!(snapped.len(), 144);
+        for mv in MOVE_VEC.iter().filter(|mv| mv.is_snapped()) {
+            assert!(!mv.available_in_gen8, "{} is snapped but usable", mv.name());
+        }
+        assert_eq!(MOVE_VEC.iter().filter(|mv| mv.is_snapped()).count(), snapped.len());
+
+        let hidden_power = POKEDEX.move_by_name("Hidden Power").unwrap();
+        assert!(hidden_power.is_snapped());
+        assert!(!hidden_power.is_usable());
+        assert!(!POKEDEX.move_by_name("Body Press").unwrap().is_snapped());
+    }
+
+    #[test]
+    fn resolve_test() {
+        let mv = MoveId::from("Body Press").resolve().expect("Could not resolve Body Press");
+        assert_eq!(mv.name(), "Body Press");
+        assert_eq!(mv.category, Category::Physical);
+        assert!(MoveId::from("Body Pres").resolve().is_none());
+
+        for tr in (0..100).map(TR::new) {
+            assert!(tr.as_move().resolve().is_some(), "could not resolve {:?}", tr);
+        }
+        for tm in (0..100).map(TM::new) {
+            assert!(tm.as_move().resolve().is_some(), "could not resolve {:?}", tm);
+        }
+    }
+
+    #[test]
+    fn tr_sources_test() {
+        let aura_sphere = TR::new(56);
+        for version in [GameVersion::Sword, GameVersion::Shield].iter() {
+            let sources = aura_sphere.sources(*version);
+            assert!(!sources.is_empty());
+            assert!(sources.iter().all(|source| source.expected_raids() >= 1.0));
+        }
+    }
+
+    #[test]
+    fn description_test() {
+        let descriptions = safe_load_move_descriptions().expect("Failed to load move descriptions");
+        for mv in MOVE_VEC.iter().filter(|mv| mv.name() != "———") {
+            assert!(descriptions.contains_key(mv.name()), "no description for {}", mv.name());
+            assert!(!mv.description().is_empty(), "empty description for {}", mv.name());
+        }
+
+        let pound = MoveId::from("Pound").resolve().unwrap();
+        assert_eq!(
+            pound.description(),
+            "The target is physically pounded with a long tail, a foreleg, or the like.",
+        );
+    }
+
     #[test]
     fn load_moves_test() {
         match safe_load_moves() {
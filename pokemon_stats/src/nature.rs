@@ -0,0 +1,214 @@
+use crate::pokemon::Stat;
+use num::FromPrimitive;
+use num_derive::FromPrimitive;
+use std::fmt;
+use std::str::FromStr;
+
+/// A pokemon's nature, which raises one stat by 10% and lowers another by 10%.
+/// Neutral natures, such as Serious, have no effect on stats.
+///
+/// The variants are ordered by their index in game.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, Hash)]
+pub enum Nature {
+    Hardy = 0,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    /// The default nature, which has no effect on stats.
+    #[default]
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+impl Nature {
+    pub fn iter() -> impl Iterator<Item = Nature> {
+        (0..Nature::count())
+            .filter_map(Nature::from_usize)
+    }
+
+    fn count() -> usize {
+        25
+    }
+
+    /// Finds the nature with the numeric index used in game, from 0 for Hardy to 24 for Quirky.
+    pub fn from_index(index: u32) -> Option<Nature> {
+        Nature::from_u32(index)
+    }
+
+    /// The numeric index of the nature in game.
+    pub fn index(self) -> u32 {
+        self as u32
+    }
+
+    /// The stat raised by this nature, or None for a neutral nature.
+    pub fn increased(self) -> Option<Stat> {
+        self.effects().map(|(up, _)| up)
+    }
+
+    /// The stat lowered by this nature, or None for a neutral nature.
+    pub fn decreased(self) -> Option<Stat> {
+        self.effects().map(|(_, down)| down)
+    }
+
+    /// Neutral natures do not change any stats.
+    pub fn is_neutral(self) -> bool {
+        self.effects().is_none()
+    }
+
+    /// The percent this nature scales the stat by.
+    /// 110 for the increased stat, 90 for the decreased stat and 100 otherwise.
+    pub fn percent(self, stat: Stat) -> u64 {
+        match self.effects() {
+            Some((up, _)) if up == stat => 110,
+            Some((_, down)) if down == stat => 90,
+            _ => 100,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        use Nature::*;
+        match self {
+            Hardy => "Hardy",
+            Lonely => "Lonely",
+            Brave => "Brave",
+            Adamant => "Adamant",
+            Naughty => "Naughty",
+            Bold => "Bold",
+            Docile => "Docile",
+            Relaxed => "Relaxed",
+            Impish => "Impish",
+            Lax => "Lax",
+            Timid => "Timid",
+            Hasty => "Hasty",
+            Serious => "Serious",
+            Jolly => "Jolly",
+            Naive => "Naive",
+            Modest => "Modest",
+            Mild => "Mild",
+            Quiet => "Quiet",
+            Bashful => "Bashful",
+            Rash => "Rash",
+            Calm => "Calm",
+            Gentle => "Gentle",
+            Sassy => "Sassy",
+            Careful => "Careful",
+            Quirky => "Quirky",
+        }
+    }
+
+    /// The (increased, decreased) stats for this nature.
+    fn effects(self) -> Option<(Stat, Stat)> {
+        use Nature::*;
+        use Stat::*;
+        match self {
+            Lonely => Some((Attack, Defense)),
+            Brave => Some((Attack, Speed)),
+            Adamant => Some((Attack, SpAttack)),
+            Naughty => Some((Attack, SpDefense)),
+            Bold => Some((Defense, Attack)),
+            Relaxed => Some((Defense, Speed)),
+            Impish => Some((Defense, SpAttack)),
+            Lax => Some((Defense, SpDefense)),
+            Timid => Some((Speed, Attack)),
+            Hasty => Some((Speed, Defense)),
+            Jolly => Some((Speed, SpAttack)),
+            Naive => Some((Speed, SpDefense)),
+            Modest => Some((SpAttack, Attack)),
+            Mild => Some((SpAttack, Defense)),
+            Quiet => Some((SpAttack, Speed)),
+            Rash => Some((SpAttack, SpDefense)),
+            Calm => Some((SpDefense, Attack)),
+            Gentle => Some((SpDefense, Defense)),
+            Sassy => Some((SpDefense, Speed)),
+            Careful => Some((SpDefense, SpAttack)),
+            Hardy | Docile | Serious | Bashful | Quirky => None,
+        }
+    }
+}
+
+impl fmt::Display for Nature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Returned when a string is not the name of any nature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownNature(pub String);
+
+impl fmt::Display for UnknownNature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown nature: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownNature {}
+
+impl FromStr for Nature {
+    type Err = UnknownNature;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Nature::iter()
+            .find(|nature| nature.name() == s)
+            .ok_or_else(|| UnknownNature(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nature_count_test() {
+        assert_eq!(Nature::iter().count(), 25);
+        assert!(Nature::from_usize(Nature::count()).is_none());
+    }
+
+    #[test]
+    fn nature_index_test() {
+        assert_eq!(Nature::from_index(0), Some(Nature::Hardy));
+        assert_eq!(Nature::from_index(13), Some(Nature::Jolly));
+        assert_eq!(Nature::from_index(25), None);
+        for nature in Nature::iter() {
+            assert_eq!(Nature::from_index(nature.index()), Some(nature));
+        }
+    }
+
+    #[test]
+    fn nature_effects_test() {
+        assert_eq!(Nature::Adamant.increased(), Some(Stat::Attack));
+        assert_eq!(Nature::Adamant.decreased(), Some(Stat::SpAttack));
+        assert_eq!(Nature::Timid.percent(Stat::Speed), 110);
+        assert_eq!(Nature::Timid.percent(Stat::Attack), 90);
+        assert_eq!(Nature::Timid.percent(Stat::Defense), 100);
+        assert!(Nature::Serious.is_neutral());
+        assert_eq!(Nature::iter().filter(|n| n.is_neutral()).count(), 5);
+    }
+
+    #[test]
+    fn nature_from_str_test() {
+        for nature in Nature::iter() {
+            assert_eq!(nature.to_string().parse::<Nature>(), Ok(nature));
+        }
+        assert!("Adamnt".parse::<Nature>().is_err());
+    }
+}
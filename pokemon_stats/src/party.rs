@@ -1,8 +1,13 @@
 
+use crate::error::{
+    ParseError,
+    ParseErrorKind,
+};
 use crate::pokemon::*;
 use std::collections::BTreeMap;
 
 /// A group of pokemon that can be used all at once in a battle
+#[derive(Clone, PartialEq, Debug)]
 pub struct Party {
     members: Vec<Pokemon>,
 }
@@ -39,26 +44,65 @@ impl Party {
         &self.members
     }
 
-    pub fn parse(s: &str) -> Self {
+    /// Loads a team from the Pokemon Showdown format, with sets separated by blank lines.
+    /// Fails on the first problem found in any of the sets.
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
         let mut members = Vec::new();
 
-        for pokemon_str in s.split("\n\n") {
-            let p = Pokemon::parse(pokemon_str);
-            if let Some(p) = p {
-                members.push(p);
+        for set in set_blocks(s) {
+            if members.len() >= 6 {
+                let (line_num, line) = set[0];
+                return Err(ParseError::new(line_num, line, ParseErrorKind::TooManyMembers));
             }
+            members.push(Pokemon::parse_lines(&set, &mut None)?);
         }
 
-        Party {
+        if members.is_empty() {
+            return Err(ParseError::new(1, "", ParseErrorKind::Empty));
+        }
+
+        Ok(Party {
             members,
+        })
+    }
+
+    /// Loads a team from the Pokemon Showdown format, collecting problems as warnings instead
+    /// of failing. Sets that can not be read at all, like those with an unknown species, are
+    /// left out of the team.
+    pub fn parse_lenient(s: &str) -> (Self, Vec<ParseError>) {
+        let mut members = Vec::new();
+        let mut warnings = Vec::new();
+
+        for set in set_blocks(s) {
+            if members.len() >= 6 {
+                let (line_num, line) = set[0];
+                warnings.push(ParseError::new(line_num, line, ParseErrorKind::TooManyMembers));
+                break;
+            }
+            match Pokemon::parse_lines(&set, &mut Some(&mut warnings)) {
+                Ok(p) => members.push(p),
+                Err(err) => warnings.push(err),
+            }
         }
+
+        (Party { members }, warnings)
+    }
+
+    /// Writes the team in the Pokemon Showdown format, the inverse of `parse`.
+    pub fn to_showdown(&self) -> String {
+        self.members
+            .iter()
+            .map(|p| p.to_showdown())
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
+    /// Counts how many members take each efficacy from each attacking type, including the
+    /// effect of their abilities.
     pub fn type_matchups(&self) -> BTreeMap<(Efficacy, PureType), u32> {
         let mut freq = BTreeMap::new();
         for pokemon in self.members.iter() {
-            for ty in PureType::iter() {
-                let eff = ty.against(pokemon.species());
+            for (ty, eff) in pokemon.type_matchups() {
                 *freq.entry((eff, ty)).or_insert(0) += 1;
             }
         }
@@ -78,6 +122,30 @@ impl Party {
     }
 }
 
+/// Splits text into the lines for each set, separated by blank lines.
+/// Each line is trimmed and paired with its line number, starting from 1.
+fn set_blocks(s: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !current.is_empty() {
+                blocks.push(current);
+                current = Vec::new();
+            }
+        } else {
+            current.push((i + 1, line));
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    blocks
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -105,9 +173,86 @@ mod test {
             IVs: 0 Atk  
             - Baton Pass  
             "#
-        );
+        ).unwrap();
 
         assert_eq!(3, party.members.len());
         assert_eq!("Glaceon", party.members[2].species_name());
     }
+
+    #[test]
+    fn type_matchups_test() {
+        let party = Party::parse("Vaporeon\nAbility: Water Absorb\n\nRotom\nAbility: Levitate").unwrap();
+        let matchups = party.type_matchups();
+        assert_eq!(matchups.get(&(Efficacy::Zero, PureType::Water)), Some(&1));
+        assert_eq!(matchups.get(&(Efficacy::Zero, PureType::Ground)), Some(&1));
+        assert_eq!(matchups.get(&(Efficacy::Pow2(1), PureType::Ground)), None);
+    }
+
+    #[test]
+    fn showdown_round_trip_test() {
+        let party = Party::parse(r#"Vaporeon  
+            Ability: Water Absorb  
+            EVs: 248 HP / 8 SpA / 252 SpD  
+            Calm Nature  
+            IVs: 0 Atk  
+            - Baton Pass  
+            - Acid Armor  
+            - Aqua Ring  
+            - Surf  
+
+            Dreps (Dragapult) @ Assault Vest  
+            Ability: Clear Body  
+            - Agility  
+            - Baton Pass  
+            - Sucker Punch  
+
+            Glaceon  
+            Ability: Snow Cloak  
+            IVs: 0 Atk  
+            - Baton Pass  
+            "#
+        ).unwrap();
+
+        let exported = party.to_showdown();
+        assert_eq!(Party::parse(&exported), Ok(party), "round trip failed for:\n{}", exported);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let team = r#"Vaporeon
+Ability: Water Absorb
+- Surf
+
+Dragapult
+- Dragon Dartz
+- Phantom Force
+
+Garchompp
+- Earthquake
+"#;
+
+        let err = Party::parse(team).unwrap_err();
+        assert_eq!(err.line, 6);
+        assert_eq!(err.text, "- Dragon Dartz");
+        assert_eq!(err.kind, ParseErrorKind::UnknownMove("Dragon Dartz".to_string()));
+
+        let (party, warnings) = Party::parse_lenient(team);
+        assert_eq!(2, party.members.len(), "unknown species should be left out");
+        assert_eq!(1, party.members[1].moves().len(), "unknown move should be skipped");
+        let kinds: Vec<_> = warnings.iter().map(|w| w.kind.clone()).collect();
+        assert_eq!(kinds, vec![
+            ParseErrorKind::UnknownMove("Dragon Dartz".to_string()),
+            ParseErrorKind::UnknownSpecies("Garchompp".to_string()),
+        ]);
+        assert_eq!(warnings[1].line, 9);
+    }
+
+    #[test]
+    fn too_many_members_test() {
+        let team = ["Gengar"; 7].join("\n\n");
+        let err = Party::parse(&team).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::TooManyMembers);
+        assert_eq!(err.line, 13);
+        assert!(Party::parse("").is_err());
+    }
 }
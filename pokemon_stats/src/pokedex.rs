@@ -0,0 +1,204 @@
+use crate::moves::{
+    Move,
+    MOVE_VEC,
+};
+use crate::pokemon::{
+    Species,
+    POKEMON_VEC,
+};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+lazy_static! {
+    /// Index over `POKEMON_VEC` and `MOVE_VEC`.
+    pub static ref POKEDEX: Pokedex<'static> = Pokedex::new(&POKEMON_VEC, &MOVE_VEC);
+}
+
+/// Fast lookups of species and moves by name or number.
+///
+/// Builds hash indexes over lists of species and moves once, so each lookup does not need to
+/// scan the whole list. Use `POKEDEX` for the data embedded in this crate.
+pub struct Pokedex<'a> {
+    species: &'a [Species],
+    moves: &'a [Move],
+    by_name: HashMap<&'a str, usize>,
+    /// Every form with the national dex number, base form first.
+    by_national_dex: HashMap<u32, Vec<usize>>,
+    /// Every form with the Galar dex number, base form first.
+    by_galar_dex: HashMap<u32, Vec<usize>>,
+    /// Maps a species name to the species that evolves into it.
+    pre_evolutions: HashMap<&'a str, usize>,
+    moves_by_name: HashMap<&'a str, usize>,
+    moves_by_id: HashMap<u32, usize>,
+}
+
+impl<'a> Pokedex<'a> {
+    pub fn new(species: &'a [Species], moves: &'a [Move]) -> Self {
+        let mut by_name = HashMap::new();
+        let mut pre_evolutions = HashMap::new();
+        for (i, p) in species.iter().enumerate() {
+            by_name.entry(p.name()).or_insert(i);
+            for evo in p.evolutions.iter() {
+                pre_evolutions.entry(evo.into.as_str()).or_insert(i);
+            }
+        }
+
+        let mut by_national_dex = HashMap::new();
+        let mut by_galar_dex = HashMap::new();
+        for (i, p) in species.iter().enumerate() {
+            // alternate forms use the number of their base form
+            let national = by_name
+                .get(p.base_name())
+                .map(|&base| species[base].id)
+                .unwrap_or(p.id);
+            by_national_dex.entry(national).or_insert_with(Vec::new).push(i);
+            if let Some(galar) = p.galar_dex {
+                by_galar_dex.entry(galar).or_insert_with(Vec::new).push(i);
+            }
+        }
+        for forms in by_national_dex.values_mut().chain(by_galar_dex.values_mut()) {
+            forms.sort_by_key(|&i| species[i].form());
+        }
+
+        let mut moves_by_name = HashMap::new();
+        let mut moves_by_id = HashMap::new();
+        for (i, mv) in moves.iter().enumerate() {
+            moves_by_name.entry(mv.name()).or_insert(i);
+            // the placeholder first move shares its id with Pound, so later moves win
+            moves_by_id.insert(mv.move_id, i);
+        }
+
+        Pokedex {
+            species,
+            moves,
+            by_name,
+            by_national_dex,
+            by_galar_dex,
+            pre_evolutions,
+            moves_by_name,
+            moves_by_id,
+        }
+    }
+
+    pub fn all_species(&self) -> &'a [Species] {
+        self.species
+    }
+
+    pub fn all_moves(&self) -> &'a [Move] {
+        self.moves
+    }
+
+    /// Finds a species by its full name, like "Zigzagoon" or "Zigzagoon 1" for an alternate form.
+    pub fn species(&self, name: &str) -> Option<&'a Species> {
+        self.by_name.get(name).map(|&i| &self.species[i])
+    }
+
+    /// Finds the base form of the species with the national dex number.
+    pub fn by_national_dex(&self, number: u32) -> Option<&'a Species> {
+        self.national_dex_forms(number).next()
+    }
+
+    /// Every form of the species with the national dex number, base form first.
+    pub fn national_dex_forms(&self, number: u32) -> impl Iterator<Item = &'a Species> + '_ {
+        self.forms(&self.by_national_dex, number)
+    }
+
+    /// Finds the base form of the species with the Galar dex number.
+    pub fn by_galar_dex(&self, number: u32) -> Option<&'a Species> {
+        self.galar_dex_forms(number).next()
+    }
+
+    /// Every form of the species with the Galar dex number, base form first.
+    pub fn galar_dex_forms(&self, number: u32) -> impl Iterator<Item = &'a Species> + '_ {
+        self.forms(&self.by_galar_dex, number)
+    }
+
+    /// Finds the species with the national dex number and form number, like 263 and 1 for
+    /// Galarian Zigzagoon. This is how the game data refers to species.
+    pub fn national_dex_form(&self, number: u32, form: u32) -> Option<&'a Species> {
+        self.national_dex_forms(number).find(|species| species.form() == form)
+    }
+
+    /// The national dex number of the species. Alternate forms share the number of their base form.
+    pub fn national_dex(&self, species: &Species) -> u32 {
+        self.species(species.base_name())
+            .map(|base| base.id)
+            .unwrap_or(species.id)
+    }
+
+    /// The species that evolves into the named species, if any.
+    pub fn pre_evolution(&self, name: &str) -> Option<&'a Species> {
+        self.pre_evolutions.get(name).map(|&i| &self.species[i])
+    }
+
+    /// Finds a move by name, like "Body Press".
+    pub fn move_by_name(&self, name: &str) -> Option<&'a Move> {
+        self.moves_by_name.get(name).map(|&i| &self.moves[i])
+    }
+
+    /// Finds a move by its numeric id in game, like 776 for Body Press.
+    pub fn move_by_id(&self, id: u32) -> Option<&'a Move> {
+        self.moves_by_id.get(&id).map(|&i| &self.moves[i])
+    }
+
+    fn forms<'s>(&'s self, index: &'s HashMap<u32, Vec<usize>>, number: u32) -> impl Iterator<Item = &'a Species> + 's {
+        let species = self.species;
+        index
+            .get(&number)
+            .into_iter()
+            .flat_map(move |forms| forms.iter().map(move |&i| &species[i]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn species_lookup_test() {
+        let avalugg = POKEDEX.species("Avalugg").expect("missing Avalugg");
+        assert_eq!(avalugg.name(), "Avalugg");
+        assert!(POKEDEX.species("Garchompp").is_none());
+
+        assert_eq!(POKEDEX.by_national_dex(713), Some(avalugg));
+        assert_eq!(POKEDEX.by_galar_dex(359), Some(avalugg));
+        assert!(POKEDEX.by_national_dex(0).is_none());
+    }
+
+    #[test]
+    fn forms_test() {
+        let forms: Vec<_> = POKEDEX.national_dex_forms(263).map(|p| p.name()).collect();
+        assert_eq!(forms, vec!["Zigzagoon", "Zigzagoon 1"]);
+        assert_eq!(POKEDEX.by_galar_dex(31).map(|p| p.name()), Some("Zigzagoon"));
+
+        let galarian = POKEDEX.species("Zigzagoon 1").unwrap();
+        assert_eq!(POKEDEX.national_dex(galarian), 263);
+        assert_eq!(POKEDEX.national_dex_form(263, 1), Some(galarian));
+        assert!(POKEDEX.national_dex_form(263, 2).is_none());
+    }
+
+    #[test]
+    fn every_species_indexed_test() {
+        for p in POKEMON_VEC.iter() {
+            assert_eq!(POKEDEX.species(p.name()), Some(p));
+            let national = POKEDEX.national_dex(p);
+            assert!(POKEDEX.national_dex_forms(national).any(|form| form == p), "{} not indexed", p.name());
+        }
+    }
+
+    #[test]
+    fn move_lookup_test() {
+        let mv = POKEDEX.move_by_name("Body Press").expect("missing Body Press");
+        assert_eq!(mv.name(), "Body Press");
+        assert!(POKEDEX.move_by_name("Body Pres").is_none());
+
+        assert_eq!(POKEDEX.move_by_id(776), Some(mv));
+        assert_eq!(POKEDEX.move_by_id(1).map(|mv| mv.name()), Some("Pound"));
+    }
+
+    #[test]
+    fn pre_evolution_test() {
+        assert_eq!(POKEDEX.pre_evolution("Dragapult").map(|p| p.name()), Some("Drakloak"));
+        assert!(POKEDEX.pre_evolution("Dreepy").is_none());
+    }
+}
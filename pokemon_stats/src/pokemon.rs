@@ -1,4 +1,24 @@
+pub use crate::evolution::*;
 pub use crate::moves::*;
+pub use crate::nature::*;
+use crate::abilities::{
+    Ability,
+    AbilitySlots,
+};
+use crate::encounters::{
+    sources,
+    GameVersion,
+    Source,
+};
+use crate::items::{
+    Item,
+    WildHeldItem,
+};
+use crate::pokedex::POKEDEX;
+use crate::error::{
+    ParseError,
+    ParseErrorKind,
+};
 use enumset::EnumSetType;
 use num::FromPrimitive;
 use num_derive::FromPrimitive;
@@ -59,6 +79,14 @@ impl Efficacy {
     pub fn is_super_effective(&self) -> bool {
         self > &Efficacy::Pow2(0)
     }
+
+    /// The damage multiplier, like 2.0 for super effective.
+    pub fn multiplier(self) -> f64 {
+        match self {
+            Efficacy::Zero => 0.0,
+            Efficacy::Pow2(n) => 2f64.powi(n as i32),
+        }
+    }
 }
 
 /// A single type in the type chart
@@ -98,6 +126,32 @@ impl PureType {
         18
     }
 
+    /// Converts from the number the games use for each type.
+    pub fn from_game_index(index: u8) -> Option<PureType> {
+        use PureType::*;
+        match index {
+            0 => Some(Normal),
+            1 => Some(Fighting),
+            2 => Some(Flying),
+            3 => Some(Poison),
+            4 => Some(Ground),
+            5 => Some(Rock),
+            6 => Some(Bug),
+            7 => Some(Ghost),
+            8 => Some(Steel),
+            9 => Some(Fire),
+            10 => Some(Water),
+            11 => Some(Grass),
+            12 => Some(Electric),
+            13 => Some(Psychic),
+            14 => Some(Ice),
+            15 => Some(Dragon),
+            16 => Some(Dark),
+            17 => Some(Fairy),
+            _ => None,
+        }
+    }
+
     pub fn efficacy(attack: PureType, defense: PureType) -> Efficacy {
         use PureType::*;
         use Efficacy::*;
@@ -300,6 +354,14 @@ impl PokemonType {
         PokemonType::efficacy(attack, self)
     }
 
+    /// True if one of the types is **ty**.
+    pub fn contains(self, ty: PureType) -> bool {
+        match self {
+            PokemonType::Single(t) => t == ty,
+            PokemonType::Double(t1, t2) => t1 == ty || t2 == ty,
+        }
+    }
+
     pub fn type_matchups(self) -> impl Iterator<Item = (PureType, Efficacy)> {
         PureType::iter().map(move |attack| (attack, self.against(attack)))
     }
@@ -424,6 +486,9 @@ pub enum Stat {
 /// Evolutions, such as Bulbasaur, Ivysaur and Venusaur, are different species.
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct Species {
+    /// The national dex number for base forms. Alternate forms have their own number past the
+    /// end of the national dex, see `Pokedex::national_dex`.
+    pub id: u32,
     pub name: String,
     pub stage: i64,
     #[serde(deserialize_with = "deserialize::galar_dex")]
@@ -435,6 +500,9 @@ pub struct Species {
     pub abilities: Vec<String>,
     #[serde(deserialize_with = "deserialize::pokemon_type")]
     pub types: PokemonType,
+    /// The items wild pokemon of this species can hold, with the chance out of 100. Either empty,
+    /// or three entries with chances 50, 5 and 1 and "None" for an empty slot. See
+    /// `wild_held_items`.
     pub items: Vec<(String, u64)>,
     pub exp_group: String,
     pub egg_groups: Vec<String>,
@@ -446,6 +514,7 @@ pub struct Species {
     pub egg_moves: Vec<MoveId>,
     pub tms: Vec<TM>,
     pub trs: Vec<TR>,
+    pub evolutions: Vec<Evolution>,
 }
 
 mod deserialize {
@@ -582,6 +651,16 @@ impl Species {
         self.name.as_str()
     }
 
+    /// The name without the form number, "Zigzagoon" for both "Zigzagoon" and "Zigzagoon 1".
+    pub fn base_name(&self) -> &str {
+        split_form(self.name()).0
+    }
+
+    /// The form number, 0 for the base form.
+    pub fn form(&self) -> u32 {
+        split_form(self.name()).1
+    }
+
     pub fn is_galar(&self) -> bool {
         self.galar_dex.is_some()
     }
@@ -616,12 +695,112 @@ impl Species {
         false
     }
 
+    /// The ways this species can evolve, one for each method of reaching each evolution.
+    pub fn evolutions(&self) -> &Vec<Evolution> {
+        &self.evolutions
+    }
+
+    /// The species that evolves into this one, if any.
+    pub fn pre_evolution(&self) -> Option<&'static Species> {
+        POKEDEX.pre_evolution(self.name())
+    }
+
+    /// Every species in this species' evolutionary family, starting from the first stage.
+    /// Each later stage comes after the species it evolves from.
+    pub fn evolution_line(&self) -> Vec<&'static Species> {
+        let mut first = POKEDEX.species(self.name());
+        while let Some(pre) = first.and_then(|p| p.pre_evolution()) {
+            first = Some(pre);
+        }
+
+        let mut line: Vec<&'static Species> = first.into_iter().collect();
+        let mut i = 0;
+        while i < line.len() {
+            for evo in line[i].evolutions.iter().filter_map(|evo| evo.species()) {
+                if !line.contains(&evo) {
+                    line.push(evo);
+                }
+            }
+            i += 1;
+        }
+
+        line
+    }
+
+    /// Every move this species can learn, leaving out moves that can not be used in Sword and
+    /// Shield. A move learned more than one way is repeated.
     pub fn moves<'a>(&'a self) -> impl Iterator<Item = MoveId> + 'a {
-        MoveIdIterator::new(self)
+        MoveIdIterator::new(self).filter(|mv| mv.resolve().is_none_or(Move::is_usable))
+    }
+
+    /// The full data for every move this species can learn, without duplicates.
+    pub fn learnable_moves<'a>(&'a self) -> impl Iterator<Item = &'static Move> + 'a {
+        let mut seen = std::collections::HashSet::new();
+        self.moves()
+            .filter_map(|mv| mv.resolve())
+            .filter(move |mv| seen.insert(mv.name()))
+    }
+
+    /// The moves the species knows when met at the level in the wild or with a trainer: the
+    /// last four distinct moves learned by leveling up.
+    pub fn default_moves(&self, level: u64) -> Vec<&'static Move> {
+        let mut known: Vec<&'static Move> = Vec::new();
+        let learned = self
+            .level_up_moves
+            .iter()
+            .filter(|(lvl, _)| *lvl <= level)
+            .filter_map(|(_, mv)| mv.resolve());
+        for mv in learned {
+            if let Some(i) = known.iter().position(|k| k.name() == mv.name()) {
+                known.remove(i);
+            }
+            known.push(mv);
+        }
+        let forgotten = known.len().saturating_sub(4);
+        known.split_off(forgotten)
+    }
+
+    /// The abilities the species can have, by slot.
+    pub fn ability_slots(&self) -> AbilitySlots {
+        AbilitySlots::from_names(&self.abilities)
+    }
+
+    /// Every way to obtain this species in the version: wild encounters, static encounters,
+    /// raids, or evolving an obtainable pre-evolution.
+    ///
+    /// Static encounters that can not be caught are left out. The rest are reported for both
+    /// versions, except for Zacian and Zamazenta, because the data does not say which version
+    /// they are in.
+    pub fn obtainable_at(&self, version: GameVersion) -> Vec<Source> {
+        sources(version, self)
+    }
+
+    /// The items wild pokemon of this species can hold, most likely first. An item in more than
+    /// one slot, like Munchlax's Leftovers, has the chances added together.
+    pub fn wild_held_items(&self) -> Vec<WildHeldItem> {
+        let mut held: Vec<WildHeldItem> = Vec::new();
+        for (name, chance) in self.items.iter().filter(|(name, _)| name != "None") {
+            let item = match Item::by_name(name) {
+                Some(item) => item,
+                None => continue,
+            };
+            match held.iter_mut().find(|h| h.item == item) {
+                Some(h) => h.chance += *chance as u8,
+                None => held.push(WildHeldItem {
+                    item,
+                    chance: *chance as u8,
+                }),
+            }
+        }
+        held
     }
 
+    /// True if the species can learn the move and use it in Sword and Shield.
     pub fn can_learn<M: Into<MoveId>>(&self, mv: M) -> bool {
         let mv = mv.into();
+        if mv.resolve().is_some_and(|mv| !mv.is_usable()) {
+            return false;
+        }
         self.by_level(&mv)
             || self.by_egg(&mv)
             || self.by_tm(&mv)
@@ -723,58 +902,248 @@ impl<'a> Iterator for MoveIdIterator<'a> {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+impl Gender {
+    /// The marker used on the Showdown name line, like "(M)".
+    fn marker(self) -> &'static str {
+        match self {
+            Gender::Male => "(M)",
+            Gender::Female => "(F)",
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Pokemon {
     species: Species,
     name: Option<String>,
+    gender: Option<Gender>,
     item: Option<String>,
     ability: String,
+    level: u64,
+    shiny: bool,
+    happiness: u8,
+    dynamax_level: u8,
+    gigantamax: bool,
     evs: Stats,
     ivs: Stats,
-    nature: String,
+    nature: Nature,
     move_list: Vec<Move>,
 }
 
 impl Pokemon {
+    /// A pokemon of the species at the level, knowing the moves. Everything else is set to the
+    /// same defaults `parse` uses: the first ability, no EVs, 31 IVs and full happiness.
+    pub fn new(species: &Species, level: u64, move_list: Vec<Move>) -> Pokemon {
+        Pokemon {
+            species: species.clone(),
+            name: None,
+            gender: None,
+            item: None,
+            ability: species.ability_slots().first.name().to_string(),
+            level,
+            shiny: false,
+            happiness: 255,
+            dynamax_level: 10,
+            gigantamax: false,
+            evs: Stats::zeros(),
+            ivs: Stats::all_valued(31),
+            nature: Nature::default(),
+            move_list,
+        }
+    }
+
+    /// Owned variant to give the pokemon a held item.
+    pub fn with_item<S: Into<String>>(mut self, item: S) -> Self {
+        self.item = Some(item.into());
+        self
+    }
+
+    /// Owned variant to set the gender.
+    pub fn with_gender(mut self, gender: Gender) -> Self {
+        self.gender = Some(gender);
+        self
+    }
+
+    /// Owned variant to set the ability.
+    pub fn with_ability<S: Into<String>>(mut self, ability: S) -> Self {
+        self.ability = ability.into();
+        self
+    }
+
+    /// Owned variant to set the dynamax level.
+    pub fn with_dynamax_level(mut self, dynamax_level: u8) -> Self {
+        self.dynamax_level = dynamax_level;
+        self
+    }
+
+    /// Owned variant to set the nature.
+    pub fn with_nature(mut self, nature: Nature) -> Self {
+        self.nature = nature;
+        self
+    }
+
+    /// Owned variant to set the EVs.
+    pub fn with_evs(mut self, evs: Stats) -> Self {
+        self.evs = evs;
+        self
+    }
+
+    /// Owned variant to set the IVs.
+    pub fn with_ivs(mut self, ivs: Stats) -> Self {
+        self.ivs = ivs;
+        self
+    }
+
     /// Loads a pokemon from the Pokemon Showdown format.
-    pub fn parse(s: &str) -> Option<Pokemon> {
-        let lines: Vec<&str> = s.lines().map(|l| l.trim()).collect();
-        let name_line = lines.get(0)?;
-        let (species_name, name, item) = Pokemon::parse_name_line(name_line)?;
-        let item = item.map(|i| i.to_string());
+    /// Fails on the first problem found, such as an unknown species or move.
+    pub fn parse(s: &str) -> Result<Pokemon, ParseError> {
+        Pokemon::parse_lines(&numbered_lines(s), &mut None)
+    }
 
-        let pokemon = &POKEMON_VEC;
-        let moves = &MOVE_VEC;
-        let species = pokemon.iter().find(|p| p.name() == species_name)?;
+    /// Loads a pokemon from the Pokemon Showdown format, skipping over problems that only
+    /// affect part of the set, like an unknown move. Those problems are returned as warnings.
+    /// Still fails when the species can not be found.
+    pub fn parse_lenient(s: &str) -> Result<(Pokemon, Vec<ParseError>), ParseError> {
+        let mut warnings = Vec::new();
+        let pokemon = Pokemon::parse_lines(&numbered_lines(s), &mut Some(&mut warnings))?;
+        Ok((pokemon, warnings))
+    }
+
+    /// Parses the trimmed lines of a single set, each paired with its line number.
+    /// When `warnings` is given, recoverable problems are pushed to it instead of failing.
+    pub(crate) fn parse_lines(
+        lines: &[(usize, &str)],
+        warnings: &mut Option<&mut Vec<ParseError>>,
+    ) -> Result<Pokemon, ParseError> {
+        let mut lines = lines.iter().filter(|(_, line)| !line.is_empty());
+        let &(line_num, name_line) = lines
+            .next()
+            .ok_or_else(|| ParseError::new(1, "", ParseErrorKind::Empty))?;
+        let NameLine { species: species_name, name, gender, item } = Pokemon::parse_name_line(name_line)
+            .ok_or_else(|| ParseError::new(line_num, name_line, ParseErrorKind::MalformedNameLine))?;
+
+        let species = POKEDEX.species(species_name)
+            .ok_or_else(|| ParseError::new(
+                line_num,
+                name_line,
+                ParseErrorKind::UnknownSpecies(species_name.to_string()),
+            ))?;
+
+        let item = match item {
+            Some(item) if Item::by_name(item).is_none() => {
+                let kind = ParseErrorKind::UnknownItem(item.to_string());
+                recover(warnings, ParseError::new(line_num, name_line, kind))?;
+                None
+            }
+            item => item.map(|i| i.to_string()),
+        };
 
-        let mut ability = "".to_string();
+        let slots = species.ability_slots();
+        let mut ability = slots.first.name().to_string();
+        let mut level = 100;
+        let mut shiny = false;
+        let mut happiness = 255;
+        let mut dynamax_level = 10;
+        let mut gigantamax = false;
         let mut evs = Stats::zeros();
         let mut ivs = Stats::all_valued(31);
         let mut move_list = Vec::new();
-        let mut nature = "Serious".to_string(); // by default, Serious nature, with no changes to stats.
-        for line in lines {
+        let mut nature = Nature::default();
+        for &(line_num, line) in lines {
+            let error = |kind| ParseError::new(line_num, line, kind);
             if let Some(abl) = Pokemon::parse_ability(line) {
-                ability = abl.to_string();
-            } else if let Some(new_evs) = Pokemon::parse_evs(line) {
-                evs = new_evs;
-            } else if let Some(new_ivs) = Pokemon::parse_ivs(line) {
-                ivs = new_ivs;
+                if slots.contains(abl) {
+                    ability = abl.to_string();
+                } else if Ability::by_name(abl).is_some() {
+                    recover(warnings, error(ParseErrorKind::IllegalAbility(abl.to_string())))?
+                } else {
+                    recover(warnings, error(ParseErrorKind::UnknownAbility(abl.to_string())))?
+                }
+            } else if let Some(lvl) = after_prefix(line, "Level: ") {
+                match Pokemon::parse_level(lvl) {
+                    Ok(new_level) => level = new_level,
+                    Err(kind) => recover(warnings, error(kind))?,
+                }
+            } else if let Some(value) = after_prefix(line, "Shiny: ") {
+                match parse_yes_no(value) {
+                    Ok(new_shiny) => shiny = new_shiny,
+                    Err(kind) => recover(warnings, error(kind))?,
+                }
+            } else if let Some(value) = after_prefix(line, "Happiness: ") {
+                match value.parse::<u8>() {
+                    Ok(new_happiness) => happiness = new_happiness,
+                    Err(_) => {
+                        let kind = ParseErrorKind::InvalidHappiness(value.to_string());
+                        recover(warnings, error(kind))?
+                    }
+                }
+            } else if let Some(value) = after_prefix(line, "Dynamax Level: ") {
+                match value.parse::<u8>() {
+                    Ok(new_level) if new_level <= 10 => dynamax_level = new_level,
+                    _ => {
+                        let kind = ParseErrorKind::InvalidDynamaxLevel(value.to_string());
+                        recover(warnings, error(kind))?
+                    }
+                }
+            } else if let Some(value) = after_prefix(line, "Gigantamax: ") {
+                match parse_yes_no(value) {
+                    Ok(new_gigantamax) => gigantamax = new_gigantamax,
+                    Err(kind) => recover(warnings, error(kind))?,
+                }
+            } else if let Some(ev_str) = after_prefix(line, "EVs: ") {
+                match Pokemon::parse_evs(ev_str) {
+                    Ok(new_evs) => evs = new_evs,
+                    Err(kind) => recover(warnings, error(kind))?,
+                }
+            } else if let Some(iv_str) = after_prefix(line, "IVs: ") {
+                match Pokemon::parse_ivs(iv_str) {
+                    Ok(new_ivs) => ivs = new_ivs,
+                    Err(kind) => recover(warnings, error(kind))?,
+                }
             } else if let Some(mv_name) = after_prefix(line, "- ") {
-                let move_id = MoveId::from(mv_name);
-                let mv = moves.iter().find(|m| m.id == move_id);
-                if let Some(mv) = mv {
-                    move_list.push(mv.clone());
+                match POKEDEX.move_by_name(mv_name) {
+                    _ if move_list.len() >= 4 => {
+                        recover(warnings, error(ParseErrorKind::TooManyMoves))?
+                    }
+                    Some(mv) if !mv.is_usable() => {
+                        let kind = ParseErrorKind::UnusableMove(mv.name().to_string());
+                        recover(warnings, error(kind))?
+                    }
+                    Some(mv) => move_list.push(mv.clone()),
+                    None => {
+                        let kind = ParseErrorKind::UnknownMove(mv_name.to_string());
+                        recover(warnings, error(kind))?
+                    }
                 }
             } else if let Some(new_nature) = before_suffix(line, " Nature") {
-                nature = new_nature.to_string();
+                match new_nature.parse() {
+                    Ok(new_nature) => nature = new_nature,
+                    Err(UnknownNature(name)) => {
+                        recover(warnings, error(ParseErrorKind::UnknownNature(name)))?
+                    }
+                }
+            } else {
+                recover(warnings, error(ParseErrorKind::UnrecognizedLine))?
             }
         }
 
-        Some(Pokemon {
+        Ok(Pokemon {
             species: species.clone(),
             name: name.map(|n| n.to_string()),
+            gender,
             item,
             ability,
+            level,
+            shiny,
+            happiness,
+            dynamax_level,
+            gigantamax,
             evs,
             ivs,
             nature,
@@ -782,10 +1151,64 @@ impl Pokemon {
         })
     }
 
+    /// Writes the pokemon in the Pokemon Showdown format, the inverse of `parse`.
+    /// EVs are only listed when they are not 0, and IVs when they are not 31.
+    pub fn to_showdown(&self) -> String {
+        let mut out = String::new();
+
+        match self.nickname() {
+            Some(name) => out.push_str(&format!("{} ({})", name, self.species_name())),
+            None => out.push_str(self.species_name()),
+        }
+        if let Some(gender) = self.gender {
+            out.push_str(&format!(" {}", gender.marker()));
+        }
+        if let Some(item) = self.item() {
+            out.push_str(&format!(" @ {}", item));
+        }
+        out.push('\n');
+
+        if !self.ability.is_empty() {
+            out.push_str(&format!("Ability: {}\n", self.ability));
+        }
+        if self.level != 100 {
+            out.push_str(&format!("Level: {}\n", self.level));
+        }
+        if self.shiny {
+            out.push_str("Shiny: Yes\n");
+        }
+        if self.happiness != 255 {
+            out.push_str(&format!("Happiness: {}\n", self.happiness));
+        }
+        if self.dynamax_level != 10 {
+            out.push_str(&format!("Dynamax Level: {}\n", self.dynamax_level));
+        }
+        if self.gigantamax {
+            out.push_str("Gigantamax: Yes\n");
+        }
+        if let Some(evs) = Pokemon::stats_line(&self.evs, 0) {
+            out.push_str(&format!("EVs: {}\n", evs));
+        }
+        out.push_str(&format!("{} Nature\n", self.nature));
+        if let Some(ivs) = Pokemon::stats_line(&self.ivs, 31) {
+            out.push_str(&format!("IVs: {}\n", ivs));
+        }
+        for mv in self.move_list.iter() {
+            out.push_str(&format!("- {}\n", mv.name()));
+        }
+
+        out
+    }
+
     pub fn nickname(&self) -> Option<&str> {
         self.name.as_ref().map(|n| n.as_str())
     }
 
+    /// The gender given on the name line. None when not specified, or the species is genderless.
+    pub fn gender(&self) -> Option<Gender> {
+        self.gender
+    }
+
     pub fn species_name(&self) -> &str {
         self.species.name()
     }
@@ -798,35 +1221,170 @@ impl Pokemon {
         self.item.as_ref().map(|i| i.as_str())
     }
 
+    /// The full data for the held item.
+    pub fn held_item(&self) -> Option<&'static Item> {
+        self.item.as_ref().and_then(|item| Item::by_name(item))
+    }
+
     pub fn ability(&self) -> &str {
         self.ability.as_str()
     }
 
+    /// The pokemon's level, from 1 to 100. Defaults to 100 when the set does not give one.
+    pub fn level(&self) -> u64 {
+        self.level
+    }
+
+    pub fn is_shiny(&self) -> bool {
+        self.shiny
+    }
+
+    /// Happiness (friendship) from 0 to 255. Defaults to 255, the maximum.
+    pub fn happiness(&self) -> u8 {
+        self.happiness
+    }
+
+    /// Dynamax level from 0 to 10. Defaults to 10, the maximum.
+    pub fn dynamax_level(&self) -> u8 {
+        self.dynamax_level
+    }
+
+    /// Can this pokemon Gigantamax instead of Dynamax.
+    pub fn is_gigantamax(&self) -> bool {
+        self.gigantamax
+    }
+
+    /// The in game stats of the pokemon at its level.
     pub fn stats(&self) -> Stats {
+        self.stats_at_level(self.level)
+    }
+
+    /// The in game stats of the pokemon at the given level.
+    /// Uses the stat formula from generation 3 onward, including the effect of the nature.
+    pub fn stats_at_level(&self, level: u64) -> Stats {
+        let base = &self.species.base_stats;
+        let hp = if self.species.name() == "Shedinja" {
+            // Shedinja always has exactly 1 hp
+            1
+        } else {
+            hp_stat(base.hp, self.ivs.hp, self.evs.hp, level)
+        };
+        let stat = |stat, base, iv, ev| {
+            other_stat(base, iv, ev, level, self.nature.percent(stat))
+        };
+
+        Stats {
+            hp,
+            attack: stat(Stat::Attack, base.attack, self.ivs.attack, self.evs.attack),
+            defense: stat(Stat::Defense, base.defense, self.ivs.defense, self.evs.defense),
+            sp_attack: stat(Stat::SpAttack, base.sp_attack, self.ivs.sp_attack, self.evs.sp_attack),
+            sp_defense: stat(Stat::SpDefense, base.sp_defense, self.ivs.sp_defense, self.evs.sp_defense),
+            speed: stat(Stat::Speed, base.speed, self.ivs.speed, self.evs.speed),
+        }
+    }
+
+    /// The sum of the base stats, evs and ivs.
+    /// This does not match any value shown in game, see `stats` for that.
+    pub fn raw_stat_sum(&self) -> Stats {
         self.evs + self.ivs + self.species.base_stats
     }
 
-    pub fn nature(&self) -> &str {
-        self.nature.as_str()
+    pub fn nature(&self) -> Nature {
+        self.nature
+    }
+
+    pub fn evs(&self) -> Stats {
+        self.evs
+    }
+
+    pub fn ivs(&self) -> Stats {
+        self.ivs
     }
 
     pub fn moves(&self) -> &Vec<Move> {
         &self.move_list
     }
 
+    /// How effective an attack of the type is against this pokemon, including its ability.
+    ///
+    /// Abilities that absorb a type, like Levitate or Water Absorb, make the pokemon immune.
+    /// Thick Fat and Heatproof count as a resistance, and Fluffy as a weakness to Fire. With
+    /// Wonder Guard, only super effective attacks do damage. See `damage_modifier` for
+    /// abilities that change damage by other amounts.
+    pub fn defensive_efficacy(&self, attack: PureType) -> Efficacy {
+        self.ability_efficacy(attack, attack.against(&self.species))
+    }
+
+    /// Every attacking type with how effective it is against this pokemon, including its ability.
+    pub fn type_matchups(&self) -> impl Iterator<Item = (PureType, Efficacy)> + '_ {
+        PureType::iter().map(move |attack| (attack, self.defensive_efficacy(attack)))
+    }
+
+    /// How effective **mv** is against this pokemon, including special moves like Freeze-Dry
+    /// and this pokemon's ability.
+    pub fn move_efficacy(&self, mv: &Move) -> Efficacy {
+        self.ability_efficacy(mv.move_type, mv.efficacy_against(&self.species))
+    }
+
+    /// The modifier out of 4096 this pokemon's ability puts on the damage from **mv**, on top
+    /// of `move_efficacy`. Dry Skin takes 1.25 times the damage from Fire, Filter, Solid Rock
+    /// and Prism Armor take 0.75 times the damage from super effective moves, and Fluffy halves
+    /// the damage from contact moves.
+    pub fn damage_modifier(&self, mv: &Move) -> u64 {
+        match self.ability() {
+            "Dry Skin" if mv.move_type == PureType::Fire => 5120,
+            "Filter" | "Solid Rock" | "Prism Armor" if self.move_efficacy(mv).is_super_effective() => 3072,
+            "Fluffy" if mv.makes_contact => 2048,
+            _ => 4096,
+        }
+    }
+
+    /// How much damage from **mv** is multiplied by type effectiveness and this pokemon's
+    /// ability, combining `move_efficacy` and `damage_modifier`.
+    pub fn damage_multiplier(&self, mv: &Move) -> f64 {
+        self.move_efficacy(mv).multiplier() * self.damage_modifier(mv) as f64 / 4096.0
+    }
+
+    /// Changes **efficacy**, the effect of an attack of the type on the species, for abilities.
+    fn ability_efficacy(&self, attack: PureType, efficacy: Efficacy) -> Efficacy {
+        use PureType::*;
+        match (self.ability(), attack) {
+            ("Levitate", Ground)
+            | ("Flash Fire", Fire)
+            | ("Water Absorb", Water)
+            | ("Storm Drain", Water)
+            | ("Dry Skin", Water)
+            | ("Volt Absorb", Electric)
+            | ("Lightning Rod", Electric)
+            | ("Motor Drive", Electric)
+            | ("Sap Sipper", Grass) => Efficacy::Zero,
+            ("Thick Fat", Fire) | ("Thick Fat", Ice) | ("Heatproof", Fire) => efficacy * Efficacy::Pow2(-1),
+            ("Fluffy", Fire) => efficacy * Efficacy::Pow2(1),
+            ("Wonder Guard", _) if !efficacy.is_super_effective() => Efficacy::Zero,
+            _ => efficacy,
+        }
+    }
+
     pub fn has_super_effective_attack(&self, defender: &Species) -> bool {
         for mv in self.move_list.iter() {
-            if mv.is_attack() && mv.move_type.against(defender).is_super_effective() {
+            if mv.is_attack() && mv.efficacy_against(defender).is_super_effective() {
                 return true;
             }
         }
         false
     }
 
-    fn parse_name_line(line: &str) -> Option<(&str, Option<&str>, Option<&str>)> {
+    fn parse_name_line(line: &str) -> Option<NameLine<'_>> {
         let mut parts = line.trim().split(" @ ");
-        let name_species = parts.next()?;
+        let name_species = parts.next()?.trim();
         let item = parts.next();
+        let (name_species, gender) = if let Some(rest) = before_suffix(name_species, " (M)") {
+            (rest, Some(Gender::Male))
+        } else if let Some(rest) = before_suffix(name_species, " (F)") {
+            (rest, Some(Gender::Female))
+        } else {
+            (name_species, None)
+        };
         let mut parts = name_species.split(" (");
         let (species, name) = match (parts.next(), parts.next()) {
             (None, None) => None,
@@ -840,49 +1398,162 @@ impl Pokemon {
             }
         }?;
 
-        Some((species, name, item))
+        Some(NameLine {
+            species,
+            name,
+            gender,
+            item,
+        })
     }
 
     fn parse_ability(line: &str) -> Option<&str> {
         after_prefix(line, "Ability: ")
     }
 
-    fn parse_evs(line: &str) -> Option<Stats> {
-        let ev_str = after_prefix(line, "EVs: ")?;
-        let evs = Stats::zeros();
-        Pokemon::update_stats(evs, ev_str)
+    fn parse_level(s: &str) -> Result<u64, ParseErrorKind> {
+        match s.parse::<u64>() {
+            Ok(level) if (1..=100).contains(&level) => Ok(level),
+            _ => Err(ParseErrorKind::InvalidLevel(s.to_string())),
+        }
     }
 
-    fn parse_ivs(line: &str) -> Option<Stats> {
-        let iv_str = after_prefix(line, "IVs: ")?;
+    fn parse_evs(ev_str: &str) -> Result<Stats, ParseErrorKind> {
+        let evs = Pokemon::update_stats(Stats::zeros(), ev_str, 252)?;
+        if evs.total() > 510 {
+            return Err(ParseErrorKind::EvTotalOver510(evs.total()));
+        }
+        Ok(evs)
+    }
+
+    fn parse_ivs(iv_str: &str) -> Result<Stats, ParseErrorKind> {
         let default_ivs = Stats::all_valued(31);
-        Pokemon::update_stats(default_ivs, iv_str)
+        Pokemon::update_stats(default_ivs, iv_str, 31)
+    }
+
+    /// Formats the stats that differ from the default, like "252 HP / 4 Atk".
+    /// Returns None when every stat is the default.
+    fn stats_line(stats: &Stats, default: u64) -> Option<String> {
+        let labeled = [
+            (stats.hp, "HP"),
+            (stats.attack, "Atk"),
+            (stats.defense, "Def"),
+            (stats.sp_attack, "SpA"),
+            (stats.sp_defense, "SpD"),
+            (stats.speed, "Spe"),
+        ];
+        let parts: Vec<String> = labeled
+            .iter()
+            .filter(|(value, _)| *value != default)
+            .map(|(value, label)| format!("{} {}", value, label))
+            .collect();
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(" / "))
+        }
     }
 
     /// Updates the given stats by setting any stat in the given **line**.
-    fn update_stats(mut stats: Stats, line: &str) -> Option<Stats> {
+    /// Each value must be at most **max**.
+    fn update_stats(mut stats: Stats, line: &str, max: u64) -> Result<Stats, ParseErrorKind> {
         for stat_str in line.split(" / ") {
-            let parts: Vec<_> = stat_str.split(" ").collect();
-            let value = parts.get(0)?
+            let parts: Vec<_> = stat_str.trim().split(' ').collect();
+            if parts.len() != 2 {
+                return Err(ParseErrorKind::MalformedStats);
+            }
+            let value = parts[0]
                 .parse::<u64>()
-                .ok()?;
-            let stat = parts.get(1)?;
+                .map_err(|_| ParseErrorKind::MalformedStats)?;
+            if value > max {
+                return Err(ParseErrorKind::StatOutOfRange(value));
+            }
 
-            match *stat {
+            match parts[1] {
                 "HP" => stats.hp = value,
                 "Atk" => stats.attack = value,
                 "Def" => stats.defense = value,
                 "SpA" => stats.sp_attack = value,
                 "SpD" => stats.sp_defense = value,
                 "Spe" => stats.speed = value,
-                _ => return None,
+                stat => return Err(ParseErrorKind::UnknownStat(stat.to_string())),
             }
         }
 
-        Some(stats)
+        Ok(stats)
     }
 }
 
+/// Splits a species name into the base name and form number, like ("Zigzagoon", 1) for
+/// "Zigzagoon 1".
+fn split_form(name: &str) -> (&str, u32) {
+    let mut parts = name.rsplitn(2, ' ');
+    match (parts.next().map(|form| form.parse::<u32>()), parts.next()) {
+        (Some(Ok(form)), Some(base)) => (base, form),
+        _ => (name, 0),
+    }
+}
+
+/// Converts a "Species-form" name used by the raw data files to the name used by
+/// `Species::name`. The base form drops the suffix ("Ivysaur-0" is "Ivysaur"), other forms use
+/// a space ("Raichu-1" is "Raichu 1"). Names without a form, like "Jangmo-o", are unchanged.
+pub(crate) fn form_name(species_form: &str) -> String {
+    let mut parts = species_form.rsplitn(2, '-');
+    match (parts.next().map(|form| form.parse::<u32>()), parts.next()) {
+        (Some(Ok(0)), Some(name)) => name.to_string(),
+        (Some(Ok(form)), Some(name)) => format!("{} {}", name, form),
+        _ => species_form.to_string(),
+    }
+}
+
+/// The parts of the first line of a Showdown set, like "Nickname (Species) (M) @ Item".
+#[derive(Debug, PartialEq)]
+struct NameLine<'a> {
+    species: &'a str,
+    name: Option<&'a str>,
+    gender: Option<Gender>,
+    item: Option<&'a str>,
+}
+
+/// Reads the "Yes" or "No" used by Showdown for flags like Shiny.
+fn parse_yes_no(s: &str) -> Result<bool, ParseErrorKind> {
+    match s {
+        "Yes" => Ok(true),
+        "No" => Ok(false),
+        _ => Err(ParseErrorKind::InvalidYesNo(s.to_string())),
+    }
+}
+
+/// Splits text into trimmed lines, each paired with its line number starting from 1.
+fn numbered_lines(s: &str) -> Vec<(usize, &str)> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .collect()
+}
+
+/// Handles a problem that only affects part of a set.
+/// Records it when collecting warnings, otherwise fails with it.
+fn recover(warnings: &mut Option<&mut Vec<ParseError>>, err: ParseError) -> Result<(), ParseError> {
+    match warnings {
+        Some(warnings) => {
+            warnings.push(err);
+            Ok(())
+        }
+        None => Err(err),
+    }
+}
+
+fn hp_stat(base: u64, iv: u64, ev: u64, level: u64) -> u64 {
+    (2 * base + iv + ev / 4) * level / 100 + level + 10
+}
+
+/// Calculates any stat other than hp. `nature_percent` is 110 for a boosting nature, 90 for a
+/// hindering nature and 100 otherwise.
+fn other_stat(base: u64, iv: u64, ev: u64, level: u64, nature_percent: u64) -> u64 {
+    ((2 * base + iv + ev / 4) * level / 100 + 5) * nature_percent / 100
+}
+
 fn after_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
     if s.starts_with(prefix) {
         Some(s.trim_start_matches(prefix))
@@ -987,6 +1658,169 @@ mod test {
         }
     }
 
+    #[test]
+    fn split_form_test() {
+        assert_eq!(split_form("Zigzagoon"), ("Zigzagoon", 0));
+        assert_eq!(split_form("Zigzagoon 1"), ("Zigzagoon", 1));
+        assert_eq!(split_form("Mr. Mime 1"), ("Mr. Mime", 1));
+        assert_eq!(split_form("Type: Null"), ("Type: Null", 0));
+    }
+
+    #[test]
+    fn learnable_moves_test() {
+        let avalugg = POKEDEX.species("Avalugg").unwrap();
+        let moves: Vec<_> = avalugg.learnable_moves().collect();
+        assert!(moves.iter().any(|mv| mv.name() == "Body Press"));
+        assert_eq!(avalugg.moves().count(), avalugg.moves().filter_map(|mv| mv.resolve()).count(),
+            "every move should resolve");
+
+        let mut names: Vec<_> = moves.iter().map(|mv| mv.name()).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), moves.len(), "moves should not repeat");
+        assert!(!avalugg.can_learn("Return"));
+        assert!(avalugg.can_learn("Body Press"));
+
+        let strongest_stab = avalugg.learnable_moves()
+            .filter(|mv| mv.is_attack() && mv.move_type == Ice)
+            .max_by_key(|mv| mv.power)
+            .unwrap();
+        assert_eq!(strongest_stab.name(), "Blizzard");
+    }
+
+    #[test]
+    fn form_name_test() {
+        assert_eq!(form_name("Ivysaur-0"), "Ivysaur");
+        assert_eq!(form_name("Raichu-1"), "Raichu 1");
+        assert_eq!(form_name("Hakamo-o-0"), "Hakamo-o");
+        assert_eq!(form_name("Jangmo-o"), "Jangmo-o");
+        assert_eq!(form_name("Zigzagoon"), "Zigzagoon");
+    }
+
+    #[test]
+    fn evolution_test() {
+        let find = |name| POKEMON_VEC.iter().find(|p| p.name() == name).unwrap();
+
+        let bulbasaur = find("Bulbasaur");
+        assert_eq!(bulbasaur.evolutions().len(), 1);
+        assert_eq!(bulbasaur.evolutions()[0].into, "Ivysaur");
+        assert_eq!(bulbasaur.evolutions()[0].method, EvolutionMethod::LevelUp(16));
+        assert_eq!(bulbasaur.pre_evolution(), None);
+        assert_eq!(find("Venusaur").pre_evolution(), Some(find("Ivysaur")));
+
+        let names = |line: Vec<&'static Species>| line.iter().map(|p| p.name()).collect::<Vec<_>>();
+        assert_eq!(names(find("Drakloak").evolution_line()), vec!["Dreepy", "Drakloak", "Dragapult"]);
+        assert_eq!(names(find("Sirfetch’d").evolution_line()), vec!["Farfetch’d 1", "Sirfetch’d"]);
+
+        let eevee_line = find("Vaporeon").evolution_line();
+        assert_eq!(eevee_line[0].name(), "Eevee");
+        assert_eq!(eevee_line.len(), 9);
+        assert!(find("Eevee").evolutions().iter()
+            .any(|evo| evo.method == EvolutionMethod::LevelUpAffectionMoveType(Fairy)));
+    }
+
+    #[test]
+    fn default_moves_test() {
+        let bulbasaur = POKEMON_VEC.iter().find(|p| p.name() == "Bulbasaur").unwrap();
+        let names = |level| bulbasaur.default_moves(level).iter().map(|mv| mv.name()).collect::<Vec<_>>();
+        assert_eq!(names(1), vec!["Tackle", "Growl"]);
+        assert_eq!(names(9), vec!["Growl", "Vine Whip", "Growth", "Leech Seed"]);
+        assert_eq!(names(15), vec!["Leech Seed", "Razor Leaf", "Poison Powder", "Sleep Powder"]);
+    }
+
+    #[test]
+    fn wild_held_items_test() {
+        let butterfree = POKEDEX.species("Butterfree").unwrap();
+        let held = butterfree.wild_held_items();
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].item.name(), "Silver Powder");
+        assert_eq!(held[0].chance, 5);
+
+        let munchlax = POKEDEX.species("Munchlax").unwrap();
+        let held = munchlax.wild_held_items();
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].item.name(), "Leftovers");
+        assert_eq!(held[0].chance, 55);
+
+        for species in POKEMON_VEC.iter() {
+            for (name, _) in species.items.iter().filter(|(name, _)| name != "None") {
+                assert!(Item::by_name(name).is_some(), "{} holds unknown item {}", species.name(), name);
+            }
+        }
+    }
+
+    #[test]
+    fn default_ability_test() {
+        let snorlax = Pokemon::parse("Snorlax\n- Body Slam").unwrap();
+        assert_eq!(snorlax.ability(), "Immunity");
+        let snorlax = Pokemon::parse("Snorlax\nAbility: Gluttony").unwrap();
+        assert_eq!(snorlax.ability(), "Gluttony");
+        let snorlax = Pokemon::new(POKEDEX.species("Snorlax").unwrap(), 50, vec![]);
+        assert_eq!(snorlax.ability(), "Immunity");
+    }
+
+    #[test]
+    fn super_effective_attack_test() {
+        let freeze_dry = POKEDEX.move_by_name("Freeze-Dry").unwrap().clone();
+        let lapras = Pokemon::new(POKEDEX.species("Lapras").unwrap(), 50, vec![freeze_dry]);
+        assert!(lapras.has_super_effective_attack(POKEDEX.species("Seismitoad").unwrap()));
+        assert!(!lapras.has_super_effective_attack(POKEDEX.species("Corviknight").unwrap()));
+    }
+
+    #[test]
+    fn defensive_efficacy_test() {
+        let pokemon = |name, ability| Pokemon::new(POKEDEX.species(name).unwrap(), 50, vec![])
+            .with_ability(ability);
+
+        let rotom = pokemon("Rotom 2", "Levitate");
+        assert_eq!(rotom.defensive_efficacy(Ground), Efficacy::Zero);
+        assert_eq!(rotom.defensive_efficacy(Grass), Efficacy::Pow2(1));
+        let vaporeon = pokemon("Vaporeon", "Water Absorb");
+        assert_eq!(vaporeon.defensive_efficacy(Water), Efficacy::Zero);
+        assert_eq!(pokemon("Vaporeon", "Hydration").defensive_efficacy(Water), Efficacy::Pow2(-1));
+        let snorlax = pokemon("Snorlax", "Thick Fat");
+        assert_eq!(snorlax.defensive_efficacy(Ice), Efficacy::Pow2(-1));
+        assert_eq!(snorlax.defensive_efficacy(Fighting), Efficacy::Pow2(1));
+        let shedinja = pokemon("Shedinja", "Wonder Guard");
+        assert_eq!(shedinja.defensive_efficacy(Fire), Efficacy::Pow2(1));
+        assert_eq!(shedinja.defensive_efficacy(Water), Efficacy::Zero);
+        assert_eq!(shedinja.type_matchups().filter(|(_, eff)| eff.is_effective()).count(), 5);
+    }
+
+    #[test]
+    fn damage_multiplier_test() {
+        let mv = |name| POKEDEX.move_by_name(name).unwrap();
+        let pokemon = |name, ability| Pokemon::new(POKEDEX.species(name).unwrap(), 50, vec![])
+            .with_ability(ability);
+
+        let toxicroak = pokemon("Toxicroak", "Dry Skin");
+        assert_eq!(toxicroak.damage_multiplier(mv("Flamethrower")), 1.25);
+        assert_eq!(toxicroak.damage_multiplier(mv("Surf")), 0.0);
+        let rhyperior = pokemon("Rhyperior", "Solid Rock");
+        assert_eq!(rhyperior.damage_multiplier(mv("Surf")), 3.0);
+        let bewear = pokemon("Bewear", "Fluffy");
+        assert_eq!(bewear.damage_multiplier(mv("Tackle")), 0.5);
+        assert_eq!(bewear.damage_multiplier(mv("Flamethrower")), 2.0);
+        assert_eq!(bewear.damage_modifier(mv("Fire Punch")), 2048);
+        assert_eq!(bewear.move_efficacy(mv("Fire Punch")), Efficacy::Pow2(1));
+    }
+
+    #[test]
+    fn obtainable_at_test() {
+        let dreepy = POKEMON_VEC.iter().find(|p| p.name() == "Dreepy").unwrap();
+        let sources = dreepy.obtainable_at(GameVersion::Sword);
+        assert!(sources.iter().any(|source| match source {
+            Source::Wild(encounter) => encounter.table.weather == crate::encounters::Weather::Thunderstorm,
+            _ => false,
+        }));
+
+        let eternamax = POKEDEX.species("Eternatus 1").unwrap();
+        assert!(!eternamax
+            .obtainable_at(GameVersion::Sword)
+            .iter()
+            .any(|source| matches!(source, Source::Static(_))));
+    }
+
     #[test]
     fn parse_pokemon_test() {
         let example = r#"Glug (Avalugg) @ Heavy-Duty Boots
@@ -1024,6 +1858,7 @@ Impish Nature
             speed: 0,
         };
         assert_eq!(pokemon.ivs, expected_ivs, "incorrect ivs");
+        assert_eq!(pokemon.nature(), Nature::Impish, "wrong nature");
 
         let expected_moves: Vec<_> = vec![
             "Rapid Spin",
@@ -1045,11 +1880,64 @@ Impish Nature
     #[test]
     fn name_line_test() {
         let line = "Gengar @ Life Orb  ";
-        let (species, nickname, item) = Pokemon::parse_name_line(line)
-            .expect("Couldn't parse name line");
-        assert_eq!("Gengar", species);
-        assert_eq!(None, nickname);
-        assert_eq!(Some("Life Orb"), item);
+        let expected = NameLine {
+            species: "Gengar",
+            name: None,
+            gender: None,
+            item: Some("Life Orb"),
+        };
+        assert_eq!(Pokemon::parse_name_line(line), Some(expected));
+
+        let line = "Spooky (Gengar) (F) @ Life Orb";
+        let expected = NameLine {
+            species: "Gengar",
+            name: Some("Spooky"),
+            gender: Some(Gender::Female),
+            item: Some("Life Orb"),
+        };
+        assert_eq!(Pokemon::parse_name_line(line), Some(expected));
+
+        let line = "Gengar (M)";
+        let expected = NameLine {
+            species: "Gengar",
+            name: None,
+            gender: Some(Gender::Male),
+            item: None,
+        };
+        assert_eq!(Pokemon::parse_name_line(line), Some(expected));
+    }
+
+    #[test]
+    fn extra_fields_test() {
+        let example = r#"Gengar (F) @ Life Orb
+Ability: Cursed Body
+Shiny: Yes
+Happiness: 0
+Dynamax Level: 5
+Gigantamax: Yes
+Timid Nature
+- Hex
+            "#;
+
+        let pokemon = Pokemon::parse(example).unwrap();
+        assert_eq!(pokemon.species_name(), "Gengar", "wrong species");
+        assert_eq!(pokemon.gender(), Some(Gender::Female), "wrong gender");
+        assert!(pokemon.is_shiny());
+        assert_eq!(pokemon.happiness(), 0);
+        assert_eq!(pokemon.dynamax_level(), 5);
+        assert!(pokemon.is_gigantamax());
+        assert_eq!(Pokemon::parse(&pokemon.to_showdown()), Ok(pokemon));
+
+        let pokemon = Pokemon::parse("Gengar").unwrap();
+        assert_eq!(pokemon.gender(), None);
+        assert!(!pokemon.is_shiny());
+        assert_eq!(pokemon.happiness(), 255);
+        assert_eq!(pokemon.dynamax_level(), 10);
+        assert!(!pokemon.is_gigantamax());
+
+        assert!(Pokemon::parse("Gengar\nShiny: Maybe").is_err());
+        assert!(Pokemon::parse("Gengar\nHappiness: 256").is_err());
+        assert!(Pokemon::parse("Gengar\nDynamax Level: 11").is_err());
     }
 
     #[test]
@@ -1072,4 +1960,208 @@ IVs: 0 Atk
 
     }
 
+    #[test]
+    fn parse_error_test() {
+        use ParseErrorKind::*;
+        let kind = |s| Pokemon::parse(s).unwrap_err().kind;
+
+        assert_eq!(kind(""), Empty);
+        assert_eq!(kind("Garchompp @ Life Orb"), UnknownSpecies("Garchompp".to_string()));
+        assert_eq!(kind("Gengar\n- Shadow Bal"), UnknownMove("Shadow Bal".to_string()));
+        assert_eq!(kind("Gengar\n- Hidden Power"), UnusableMove("Hidden Power".to_string()));
+        assert_eq!(kind("Gengar @ Life Orbb"), UnknownItem("Life Orbb".to_string()));
+        assert_eq!(kind("Gengar\nAbility: Levitate"), IllegalAbility("Levitate".to_string()));
+        assert_eq!(kind("Gengar\nAbility: Cursed Bodyy"), UnknownAbility("Cursed Bodyy".to_string()));
+        assert_eq!(kind("Gengar\nEVs: 252 SpA / 252 Spd"), UnknownStat("Spd".to_string()));
+        assert_eq!(kind("Gengar\nEVs: 252SpA"), MalformedStats);
+        assert_eq!(kind("Gengar\nEVs: 300 SpA"), StatOutOfRange(300));
+        assert_eq!(kind("Gengar\nIVs: 32 Atk"), StatOutOfRange(32));
+        assert_eq!(kind("Gengar\nEVs: 252 SpA / 252 Spe / 252 HP"), EvTotalOver510(756));
+        assert_eq!(kind("Gengar\nLevel: fifty"), InvalidLevel("fifty".to_string()));
+        assert_eq!(
+            kind("Gengar\n- Hex\n- Hex\n- Hex\n- Hex\n- Hex"),
+            TooManyMoves,
+        );
+        assert_eq!(kind("Gengar\nAbilty: Cursed Body"), UnrecognizedLine);
+        assert_eq!(kind("Gengar\nEV: 252 SpA"), UnrecognizedLine);
+        assert_eq!(kind("Gengar\n-Hex"), UnrecognizedLine);
+        assert_eq!(kind("Gengar\ntimid nature"), UnrecognizedLine);
+
+        let err = Pokemon::parse("Gengar\nTimid Nature\n- Shadow Bal").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.text, "- Shadow Bal");
+    }
+
+    #[test]
+    fn parse_lenient_test() {
+        let (pokemon, warnings) = Pokemon::parse_lenient("Gengar\nAdamnt Nature\n- Shadow Bal\n- Hex")
+            .unwrap();
+        assert_eq!(pokemon.nature(), Nature::Serious);
+        assert_eq!(pokemon.moves().len(), 1);
+        assert_eq!(warnings.len(), 2);
+        assert!(Pokemon::parse_lenient("Garchompp").is_err());
+
+        let (pokemon, warnings) = Pokemon::parse_lenient("Snorlax\nAbility: Levitate").unwrap();
+        assert_eq!(pokemon.ability(), "Immunity");
+        assert_eq!(warnings.len(), 1);
+
+        let (pokemon, warnings) = Pokemon::parse_lenient("Snorlax\n- Return\n- Body Slam").unwrap();
+        assert_eq!(pokemon.moves().len(), 1);
+        assert_eq!(warnings[0].kind, ParseErrorKind::UnusableMove("Return".to_string()));
+
+        let (pokemon, warnings) = Pokemon::parse_lenient("Gengar\n-Hex\n- Shadow Ball").unwrap();
+        assert_eq!(pokemon.moves().len(), 1);
+        assert_eq!(warnings, vec![ParseError::new(2, "-Hex", ParseErrorKind::UnrecognizedLine)]);
+    }
+
+    #[test]
+    fn held_item_test() {
+        let pokemon = Pokemon::parse("Gengar @ King's Rock").unwrap();
+        assert_eq!(pokemon.item(), Some("King's Rock"));
+        assert_eq!(pokemon.held_item().map(|item| item.name()), Some("King’s Rock"));
+        let pokemon = Pokemon::parse("Gengar @ Up-Grade").unwrap();
+        assert_eq!(pokemon.held_item().map(|item| item.name()), Some("Upgrade"));
+
+        let (pokemon, warnings) = Pokemon::parse_lenient("Gengar @ Life Orbb").unwrap();
+        assert_eq!(pokemon.item(), None);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn unknown_nature_test() {
+        assert!(Pokemon::parse("Gengar\nAdamnt Nature").is_err());
+        let pokemon = Pokemon::parse("Gengar").unwrap();
+        assert_eq!(pokemon.nature(), Nature::Serious, "default nature should be neutral");
+    }
+
+    #[test]
+    fn stats_test() {
+        let example = r#"Dragapult @ Choice Band
+Ability: Clear Body
+EVs: 252 Atk / 4 Def / 252 Spe
+Jolly Nature
+- Dragon Darts
+            "#;
+
+        let pokemon = Pokemon::parse(example).unwrap();
+        let expected = Stats {
+            hp: 317,
+            attack: 339,
+            defense: 187,
+            sp_attack: 212,
+            sp_defense: 186,
+            speed: 421,
+        };
+        assert_eq!(pokemon.stats(), expected, "wrong level 100 stats");
+
+        let expected = Stats {
+            hp: 163,
+            attack: 172,
+            defense: 96,
+            sp_attack: 108,
+            sp_defense: 95,
+            speed: 213,
+        };
+        assert_eq!(pokemon.stats_at_level(50), expected, "wrong level 50 stats");
+    }
+
+    #[test]
+    fn to_showdown_test() {
+        let example = r#"Glug (Avalugg) @ Heavy-Duty Boots
+Ability: Ice Body
+Level: 50
+EVs: 252 HP / 4 Atk / 252 Def
+IVs: 0 Spe
+Impish Nature
+- Rapid Spin
+- Avalanche
+- Body Press
+- Recover
+            "#;
+
+        let expected = "Glug (Avalugg) @ Heavy-Duty Boots
+Ability: Ice Body
+Level: 50
+EVs: 252 HP / 4 Atk / 252 Def
+Impish Nature
+IVs: 0 Spe
+- Rapid Spin
+- Avalanche
+- Body Press
+- Recover
+";
+        let pokemon = Pokemon::parse(example).unwrap();
+        assert_eq!(pokemon.to_showdown(), expected);
+    }
+
+    #[test]
+    fn showdown_round_trip_test() {
+        let examples = [
+            r#"Glug (Avalugg) @ Heavy-Duty Boots
+Ability: Ice Body
+EVs: 252 HP / 4 Atk / 252 Def
+IVs: 0 Spe
+Impish Nature
+- Rapid Spin
+- Avalanche
+- Body Press
+- Recover
+            "#,
+            r#"Gengar @ Life Orb  
+Ability: Cursed Body  
+EVs: 252 SpA / 4 SpD / 252 Spe  
+Timid Nature  
+IVs: 0 Atk  
+- Thunderbolt  
+- Venoshock  
+- Hex  
+- Destiny Bond  
+            "#,
+            r#"Dragapult @ Choice Band
+Ability: Clear Body
+Level: 50
+EVs: 252 Atk / 4 Def / 252 Spe
+Jolly Nature
+- Dragon Darts
+            "#,
+            "Shedinja\nEVs: 252 HP",
+            "Gengar",
+        ];
+
+        for example in examples.iter() {
+            let pokemon = Pokemon::parse(example).unwrap();
+            let exported = pokemon.to_showdown();
+            assert_eq!(Pokemon::parse(&exported), Ok(pokemon), "round trip failed for:\n{}", exported);
+        }
+    }
+
+    #[test]
+    fn level_test() {
+        let example = r#"Dragapult @ Choice Band
+Ability: Clear Body
+Level: 50
+EVs: 252 Atk / 4 Def / 252 Spe
+Jolly Nature
+- Dragon Darts
+            "#;
+
+        let pokemon = Pokemon::parse(example).unwrap();
+        assert_eq!(pokemon.level(), 50);
+        assert_eq!(pokemon.stats(), pokemon.stats_at_level(50));
+        assert_eq!(pokemon.stats().speed, 213);
+
+        let pokemon = Pokemon::parse("Dragapult").unwrap();
+        assert_eq!(pokemon.level(), 100, "level should default to 100");
+
+        assert!(Pokemon::parse("Dragapult\nLevel: 0").is_err());
+        assert!(Pokemon::parse("Dragapult\nLevel: 101").is_err());
+    }
+
+    #[test]
+    fn shedinja_hp_test() {
+        let pokemon = Pokemon::parse("Shedinja\nEVs: 252 HP").unwrap();
+        assert_eq!(pokemon.stats().hp, 1);
+        assert_eq!(pokemon.stats_at_level(50).hp, 1);
+    }
+
 }
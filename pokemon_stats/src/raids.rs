@@ -0,0 +1,479 @@
+use crate::encounters::GameVersion;
+use crate::error::DataError;
+use crate::items::Item;
+use crate::moves::{
+    TR,
+    TR_S,
+};
+use crate::pokedex::POKEDEX;
+use crate::pokemon::{
+    form_name,
+    Species,
+};
+use lazy_static::lazy_static;
+
+const SWORD_RAIDS: &str = include_str!("../../data/raw/sword_raid_encounters_and_drops.txt");
+const SHIELD_RAIDS: &str = include_str!("../../data/raw/shield_raid_encounters_and_drops.txt");
+
+/// Loads every Max Raid nest for the game, returning the parsing error.
+pub fn safe_load_nests(version: GameVersion) -> Result<Vec<Nest>, DataError> {
+    match version {
+        GameVersion::Sword => parse_nests(SWORD_RAIDS),
+        GameVersion::Shield => parse_nests(SHIELD_RAIDS),
+    }
+}
+
+lazy_static! {
+    pub static ref SWORD_NESTS: Vec<Nest> = safe_load_nests(GameVersion::Sword)
+        .expect("Could not load Sword raids");
+    pub static ref SHIELD_NESTS: Vec<Nest> = safe_load_nests(GameVersion::Shield)
+        .expect("Could not load Shield raids");
+}
+
+/// The list of pokemon that can appear in a Max Raid den.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nest {
+    pub id: u64,
+    pub encounters: Vec<RaidEncounter>,
+}
+
+/// One pokemon that can appear in a nest.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaidEncounter {
+    /// The name of the species, as used by `Species::name`.
+    pub species: String,
+    pub gigantamax: bool,
+    /// The star rating listed for the encounter, which sets its level range.
+    pub stars: u8,
+    pub min_level: u8,
+    pub max_level: u8,
+    pub gender: RaidGender,
+    pub ability: RaidAbility,
+    /// The chance, out of 100, of this encounter being picked when the den shows the given
+    /// number of stars.
+    pub selection_probabilities: Vec<(u8, u8)>,
+    /// Items given for beating the raid.
+    pub drops: Vec<Drop>,
+    /// Extra items that may be given for beating the raid.
+    pub bonus_drops: Vec<Drop>,
+}
+
+/// The gender of the pokemon in a raid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RaidGender {
+    Random,
+    Male,
+    Female,
+}
+
+/// The abilities the pokemon in a raid can have.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RaidAbility {
+    /// One of the regular abilities of the species.
+    Any,
+    /// Any ability of the species, including its hidden ability.
+    Hidden,
+}
+
+/// An item given for beating a raid, and the chance of getting it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Drop {
+    pub item: DropItem,
+    /// The chance of getting the item, out of 100.
+    pub percent: u8,
+}
+
+/// An item given for beating a raid.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DropItem {
+    TR(TR),
+    /// Any other item.
+    Item(&'static Item),
+}
+
+impl RaidEncounter {
+    pub fn species(&self) -> Option<&'static Species> {
+        POKEDEX.species(&self.species)
+    }
+
+    /// Every drop and bonus drop.
+    pub fn all_drops(&self) -> impl Iterator<Item = &Drop> {
+        self.drops.iter().chain(self.bonus_drops.iter())
+    }
+
+    /// Whether beating the raid can give the TR.
+    pub fn drops_tr(&self, tr: TR) -> bool {
+        self.all_drops().any(|drop| drop.item == DropItem::TR(tr))
+    }
+
+    /// Whether the pokemon can have its hidden ability.
+    pub fn can_have_hidden_ability(&self) -> bool {
+        self.ability == RaidAbility::Hidden
+    }
+
+    /// The fewest stars a den can show while offering this encounter.
+    pub fn min_stars(&self) -> Option<u8> {
+        self.selection_probabilities.iter().map(|&(stars, _)| stars).min()
+    }
+
+    /// The most stars a den can show while offering this encounter.
+    pub fn max_stars(&self) -> Option<u8> {
+        self.selection_probabilities.iter().map(|&(stars, _)| stars).max()
+    }
+}
+
+/// A single way to find a species in a Max Raid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RaidDen<'a> {
+    pub nest: &'a Nest,
+    pub encounter: &'a RaidEncounter,
+}
+
+/// Every nest encounter in the given version that matches the **filter**.
+pub fn find_dens<F>(version: GameVersion, filter: F) -> Vec<RaidDen<'static>>
+where
+    F: Fn(&RaidEncounter) -> bool,
+{
+    let mut found = Vec::new();
+    for nest in version.nests() {
+        for encounter in nest.encounters.iter().filter(|e| filter(e)) {
+            found.push(RaidDen {
+                nest,
+                encounter,
+            });
+        }
+    }
+
+    found
+}
+
+/// Every nest the species can appear in, in the given version.
+pub fn raid_dens(version: GameVersion, species: &Species) -> Vec<RaidDen<'static>> {
+    find_dens(version, |encounter| encounter.species == species.name())
+}
+
+/// Every raid that can drop the TR in the given version.
+pub fn tr_dens(version: GameVersion, tr: TR) -> Vec<RaidDen<'static>> {
+    find_dens(version, |encounter| encounter.drops_tr(tr))
+}
+
+/// A den star rating where a raid can drop a TR.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TrSource {
+    pub nest: &'static Nest,
+    pub encounter: &'static RaidEncounter,
+    /// The number of stars the den shows.
+    pub stars: u8,
+    /// The chance, out of 100, of the den picking this encounter.
+    pub selection_percent: u8,
+    /// The expected number of copies of the TR from one raid, out of 100. This adds up every
+    /// drop and bonus drop of the TR, so it can be more than 100.
+    pub drop_percent: u32,
+}
+
+impl TrSource {
+    /// The average number of raids in a den showing `stars` stars needed for each copy of the TR,
+    /// counting raids where the den picks a different encounter.
+    pub fn expected_raids(&self) -> f64 {
+        10_000.0 / (f64::from(self.selection_percent) * f64::from(self.drop_percent))
+    }
+}
+
+/// Every nest, star rating and drop chance for the TR in the given version.
+pub fn tr_sources(version: GameVersion, tr: TR) -> Vec<TrSource> {
+    let mut found = Vec::new();
+    for den in tr_dens(version, tr) {
+        let drop_percent = den
+            .encounter
+            .all_drops()
+            .filter(|drop| drop.item == DropItem::TR(tr))
+            .map(|drop| u32::from(drop.percent))
+            .sum();
+        for &(stars, selection_percent) in den.encounter.selection_probabilities.iter() {
+            found.push(TrSource {
+                nest: den.nest,
+                encounter: den.encounter,
+                stars,
+                selection_percent,
+                drop_percent,
+            });
+        }
+    }
+
+    found
+}
+
+/// Every raid with the species that can have its hidden ability, in the given version.
+pub fn hidden_ability_dens(version: GameVersion, species: &Species) -> Vec<RaidDen<'static>> {
+    find_dens(version, |encounter| {
+        encounter.species == species.name() && encounter.can_have_hidden_ability()
+    })
+}
+
+/// The part of an encounter that indented lines belong to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Section {
+    Details,
+    SelectionProbabilities,
+    Drops,
+    BonusDrops,
+}
+
+/// Parses a raid file.
+///
+/// Each nest starts with a line like "Nest ID: 1676046420423018998", followed by encounters
+/// like "\t2-Star Tyrogue", each with details indented twice and lists indented three times.
+fn parse_nests(s: &str) -> Result<Vec<Nest>, DataError> {
+    let mut nests: Vec<Nest> = Vec::new();
+    let mut section = Section::Details;
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim_end();
+        let error = |message: &str| DataError::new(i + 1, line, message);
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(entry) = line.strip_prefix("\t\t\t") {
+            let encounter = nests
+                .last_mut()
+                .and_then(|nest| nest.encounters.last_mut())
+                .ok_or_else(|| error("list entry outside of an encounter"))?;
+            match section {
+                Section::SelectionProbabilities => {
+                    let probability = parse_selection_probability(entry)
+                        .ok_or_else(|| error("malformed selection probability"))?;
+                    encounter.selection_probabilities.push(probability);
+                }
+                Section::Drops | Section::BonusDrops => {
+                    let drop = parse_drop(entry).map_err(error)?;
+                    if section == Section::Drops {
+                        encounter.drops.push(drop);
+                    } else {
+                        encounter.bonus_drops.push(drop);
+                    }
+                }
+                Section::Details => return Err(error("list entry outside of a list")),
+            }
+        } else if let Some(detail) = line.strip_prefix("\t\t") {
+            let encounter = nests
+                .last_mut()
+                .and_then(|nest| nest.encounters.last_mut())
+                .ok_or_else(|| error("detail outside of an encounter"))?;
+            section = Section::Details;
+            if let Some(levels) = detail.strip_prefix("Lv. ") {
+                let mut levels = levels.splitn(2, '-');
+                let min_level = levels.next().and_then(|level| level.parse::<u8>().ok());
+                let max_level = levels.next().and_then(|level| level.parse::<u8>().ok());
+                match (min_level, max_level) {
+                    (Some(min_level), Some(max_level)) => {
+                        encounter.min_level = min_level;
+                        encounter.max_level = max_level;
+                    }
+                    _ => return Err(error("malformed level range")),
+                }
+            } else if let Some(gender) = detail.strip_prefix("Gender: ") {
+                encounter.gender = match gender {
+                    "Random" => RaidGender::Random,
+                    "Male" => RaidGender::Male,
+                    "Female" => RaidGender::Female,
+                    _ => return Err(error("unknown gender")),
+                };
+            } else if let Some(ability) = detail.strip_prefix("Ability: ") {
+                encounter.ability = match ability {
+                    "Any" => RaidAbility::Any,
+                    "Hidden" => RaidAbility::Hidden,
+                    _ => return Err(error("unknown ability")),
+                };
+            } else if detail == "Selection Probabilities:" {
+                section = Section::SelectionProbabilities;
+            } else if detail == "Drops:" {
+                section = Section::Drops;
+            } else if detail == "Bonus Drops:" {
+                section = Section::BonusDrops;
+            } else {
+                return Err(error("unknown encounter detail"));
+            }
+        } else if let Some(header) = line.strip_prefix('\t') {
+            let nest = nests
+                .last_mut()
+                .ok_or_else(|| error("encounter outside of a nest"))?;
+            let encounter = parse_encounter_header(header)
+                .ok_or_else(|| error("malformed encounter"))?;
+            nest.encounters.push(encounter);
+            section = Section::Details;
+        } else {
+            let id = line
+                .strip_prefix("Nest ID: ")
+                .and_then(|id| id.parse::<u64>().ok())
+                .ok_or_else(|| error("malformed nest"))?;
+            nests.push(Nest {
+                id,
+                encounters: Vec::new(),
+            });
+        }
+    }
+
+    Ok(nests)
+}
+
+/// Parses a line like "5-Star Gigantamax Charizard" into an encounter with no details.
+fn parse_encounter_header(header: &str) -> Option<RaidEncounter> {
+    let mut parts = header.splitn(2, "-Star ");
+    let stars = parts.next()?.parse::<u8>().ok()?;
+    let name = parts.next()?;
+    let (gigantamax, name) = match name.strip_prefix("Gigantamax ") {
+        Some(name) => (true, name),
+        None => (false, name),
+    };
+
+    Some(RaidEncounter {
+        species: form_name(name),
+        gigantamax,
+        stars,
+        min_level: 0,
+        max_level: 0,
+        gender: RaidGender::Random,
+        ability: RaidAbility::Any,
+        selection_probabilities: Vec::new(),
+        drops: Vec::new(),
+        bonus_drops: Vec::new(),
+    })
+}
+
+/// Parses a line like "1-Star Desired: 35%" into the stars and percent.
+fn parse_selection_probability(s: &str) -> Option<(u8, u8)> {
+    let mut parts = s.splitn(2, "-Star Desired: ");
+    let stars = parts.next()?.parse::<u8>().ok()?;
+    let percent = parts.next()?.strip_suffix('%')?.parse::<u8>().ok()?;
+    Some((stars, percent))
+}
+
+/// Parses a line like "100% TR56 Aura Sphere" or " 80% Sitrus Berry" into a drop, returning a
+/// message for a malformed line or an unknown item.
+fn parse_drop(s: &str) -> Result<Drop, &'static str> {
+    let malformed = "malformed drop";
+    let mut parts = s.trim_start().splitn(2, "% ");
+    let percent = parts.next().and_then(|percent| percent.parse::<u8>().ok()).ok_or(malformed)?;
+    let name = parts.next().ok_or(malformed)?;
+
+    let item = match name.strip_prefix("TR") {
+        Some(tr) if tr.as_bytes().get(2) == Some(&b' ') => {
+            let id = tr[..2].parse::<u64>().ok().filter(|&id| id < 100).ok_or(malformed)?;
+            if TR_S[id as usize] != &tr[3..] {
+                return Err(malformed);
+            }
+            DropItem::TR(TR::new(id))
+        }
+        _ => DropItem::Item(Item::by_name(name).ok_or("unknown item")?),
+    };
+
+    Ok(Drop {
+        item,
+        percent,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn load_nests_test() {
+        for version in [GameVersion::Sword, GameVersion::Shield].iter() {
+            let nests = safe_load_nests(*version).expect("Could not load nests");
+            assert_eq!(nests.len(), 93);
+            for nest in nests.iter() {
+                for encounter in nest.encounters.iter() {
+                    assert!(encounter.species().is_some(), "unknown species {} in nest {}", encounter.species, nest.id);
+                    assert!(encounter.min_level <= encounter.max_level);
+                    assert!(encounter.min_stars().is_some());
+                    for drop in encounter.all_drops() {
+                        if let DropItem::Item(item) = drop.item {
+                            assert_eq!(Item::by_name(item.name()), Some(item));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_nests_test() {
+        let text = "Nest ID: 12\r\n\t5-Star Gigantamax Meowth-2\r\n\t\tLv. 55-60\r\n\t\tGender: Female\r\n\t\tAbility: Hidden\r\n\t\tSelection Probabilities:\r\n\t\t\t4-Star Desired: 10%\r\n\t\t\t5-Star Desired: 5%\r\n\t\tDrops:\r\n\t\t\t100% TR21 Reversal\r\n\t\t\t 80% Sitrus Berry\r\n\t\tBonus Drops:\r\n\t\t\t  5% Exp. Candy S\r\n\t";
+        let nests = parse_nests(text).unwrap();
+        let expected = Nest {
+            id: 12,
+            encounters: vec![RaidEncounter {
+                species: "Meowth 2".to_string(),
+                gigantamax: true,
+                stars: 5,
+                min_level: 55,
+                max_level: 60,
+                gender: RaidGender::Female,
+                ability: RaidAbility::Hidden,
+                selection_probabilities: vec![(4, 10), (5, 5)],
+                drops: vec![
+                    Drop { item: DropItem::TR(TR::new(21)), percent: 100 },
+                    Drop { item: DropItem::Item(Item::by_name("Sitrus Berry").unwrap()), percent: 80 },
+                ],
+                bonus_drops: vec![
+                    Drop { item: DropItem::Item(Item::by_name("Exp. Candy S").unwrap()), percent: 5 },
+                ],
+            }],
+        };
+        assert_eq!(nests, vec![expected]);
+
+        let err = parse_nests("\t2-Star Tyrogue").unwrap_err();
+        assert_eq!(err.line, 1);
+    }
+
+    #[test]
+    fn parse_drop_test() {
+        assert_eq!(parse_drop("100% TR56 Aura Sphere"), Ok(Drop { item: DropItem::TR(TR::new(56)), percent: 100 }));
+        let rare_candy = Item::by_name("Rare Candy").unwrap();
+        assert_eq!(parse_drop(" 25% Rare Candy"), Ok(Drop { item: DropItem::Item(rare_candy), percent: 25 }));
+        assert_eq!(parse_drop("100% TR56 Reversal"), Err("malformed drop"));
+        assert_eq!(parse_drop("100 TR56 Aura Sphere"), Err("malformed drop"));
+        assert_eq!(parse_drop(" 25% Rare Candyy"), Err("unknown item"));
+    }
+
+    #[test]
+    fn tr_dens_test() {
+        let dens = tr_dens(GameVersion::Sword, TR::new(56));
+        assert!(dens.iter().any(|den| den.nest.id == 1676046420423018998 && den.encounter.species == "Tyrogue"));
+        assert!(tr_dens(GameVersion::Shield, TR::new(56)).iter().all(|den| den.encounter.drops_tr(TR::new(56))));
+    }
+
+    #[test]
+    fn tr_sources_test() {
+        let sources = tr_sources(GameVersion::Sword, TR::new(56));
+        let tyrogue = sources
+            .iter()
+            .find(|source| source.nest.id == 1676046420423018998 && source.encounter.species == "Tyrogue")
+            .expect("Tyrogue should drop Aura Sphere");
+        assert_eq!(tyrogue.stars, 1);
+        assert_eq!(tyrogue.selection_percent, 35);
+        assert_eq!(tyrogue.drop_percent, 100);
+        assert!((tyrogue.expected_raids() - 100.0 / 35.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hidden_ability_dens_test() {
+        let ditto = POKEDEX.species("Ditto").unwrap();
+        let dens = hidden_ability_dens(GameVersion::Sword, ditto);
+        assert!(!dens.is_empty());
+        assert!(dens.iter().all(|den| den.encounter.ability == RaidAbility::Hidden));
+        assert!(dens.len() < raid_dens(GameVersion::Sword, ditto).len());
+    }
+
+    #[test]
+    fn raid_dens_test() {
+        let dreepy = POKEDEX.species("Dreepy").unwrap();
+        let dens = raid_dens(GameVersion::Sword, dreepy);
+        assert!(!dens.is_empty());
+        for den in dens {
+            assert!(den.encounter.min_stars() <= den.encounter.max_stars());
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use crate::error::DataError;
+use crate::party::Party;
+use crate::pokedex::POKEDEX;
+use crate::pokemon::{
+    form_name,
+    Move,
+    Pokemon,
+};
+use lazy_static::lazy_static;
+
+const TRAINERS_TXT: &str = include_str!("../../data/raw/sword_shield_trainers.txt");
+
+/// Loads every trainer in the games, returning the parsing error.
+pub fn safe_load_trainers() -> Result<Vec<Trainer>, DataError> {
+    parse_trainers(TRAINERS_TXT)
+}
+
+lazy_static! {
+    pub static ref TRAINERS: Vec<Trainer> = safe_load_trainers().expect("Could not load trainers");
+}
+
+/// An opponent battled in game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trainer {
+    /// The index of the trainer in the game data.
+    pub id: u32,
+    /// The title shown before the name, like "Gym Leader".
+    pub class: String,
+    pub name: String,
+    /// Flags controlling how the trainer battles.
+    pub ai: u32,
+    pub mode: BattleMode,
+    /// The prize money for winning.
+    pub money: u32,
+    pub party: Party,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BattleMode {
+    Singles,
+    Doubles,
+}
+
+/// Every trainer named **name**, like "Hop". Rematches are separate trainers.
+pub fn trainers_named(name: &str) -> Vec<&'static Trainer> {
+    TRAINERS.iter().filter(|trainer| trainer.name == name).collect()
+}
+
+/// Parses the trainer file.
+///
+/// Each trainer is a block ending with "=========". Trainers with no pokemon are placeholders
+/// and are skipped.
+fn parse_trainers(s: &str) -> Result<Vec<Trainer>, DataError> {
+    let mut trainers = Vec::new();
+    let mut block: Vec<(usize, &str)> = Vec::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim_end();
+        if line == "=========" {
+            if let Some(trainer) = parse_trainer(&block)? {
+                trainers.push(trainer);
+            }
+            block.clear();
+        } else if !line.is_empty() && line != "---" {
+            block.push((i + 1, line));
+        }
+    }
+    if let Some(trainer) = parse_trainer(&block)? {
+        trainers.push(trainer);
+    }
+
+    Ok(trainers)
+}
+
+/// Parses the lines of one trainer, each paired with its line number. Returns `None` when the
+/// trainer has no pokemon.
+///
+/// A trainer looks like:
+///
+/// ```text
+/// 002 - Lass: Lauren
+/// AI: 32 | Mode: Singles | Money: 1680
+/// Pokémon Count: 1
+/// 1: Lv06 Chewtle : Tackle
+/// ```
+fn parse_trainer(lines: &[(usize, &str)]) -> Result<Option<Trainer>, DataError> {
+    let mut lines = lines.iter();
+    let (name_line, info_line, count_line) = match (lines.next(), lines.next(), lines.next()) {
+        (Some(name), Some(info), Some(count)) => (name, info, count),
+        (None, _, _) => return Ok(None),
+        (Some(&(line_num, line)), _, _) => return Err(DataError::new(line_num, line, "incomplete trainer")),
+    };
+
+    let &(line_num, line) = name_line;
+    let mut parts = line.splitn(2, " - ");
+    let id = parts.next().and_then(|id| id.parse::<u32>().ok());
+    let mut title = parts.next().unwrap_or("").splitn(2, ": ");
+    let (id, class, name) = match (id, title.next(), title.next()) {
+        (Some(id), Some(class), Some(name)) => (id, class.to_string(), name.to_string()),
+        _ => return Err(DataError::new(line_num, line, "malformed trainer name")),
+    };
+
+    let &(line_num, line) = info_line;
+    let (ai, mode, money) = parse_info(line)
+        .ok_or_else(|| DataError::new(line_num, line, "malformed trainer info"))?;
+
+    let &(line_num, line) = count_line;
+    let count = line
+        .strip_prefix("Pokémon Count: ")
+        .and_then(|count| count.parse::<usize>().ok())
+        .ok_or_else(|| DataError::new(line_num, line, "malformed pokemon count"))?;
+
+    let mut members = Vec::with_capacity(count);
+    for &(line_num, line) in lines.take(count) {
+        let pokemon = parse_member(line).map_err(|message| DataError::new(line_num, line, message))?;
+        members.push(pokemon);
+    }
+    if members.len() < count {
+        return Err(DataError::new(line_num, line, "fewer pokemon than the count"));
+    }
+
+    match Party::from_vec(members) {
+        Some(party) => Ok(Some(Trainer {
+            id,
+            class,
+            name,
+            ai,
+            mode,
+            money,
+            party,
+        })),
+        None if count == 0 => Ok(None),
+        None => Err(DataError::new(line_num, line, "too many pokemon")),
+    }
+}
+
+/// Parses a line like "AI: 32 | Mode: Singles | Money: 1680".
+fn parse_info(s: &str) -> Option<(u32, BattleMode, u32)> {
+    let mut parts = s.split(" | ");
+    let ai = parts.next()?.strip_prefix("AI: ")?.parse::<u32>().ok()?;
+    let mode = match parts.next()?.strip_prefix("Mode: ")? {
+        "Singles" => BattleMode::Singles,
+        "Doubles" => BattleMode::Doubles,
+        _ => return None,
+    };
+    let money = parts.next()?.strip_prefix("Money: ")?.parse::<u32>().ok()?;
+    Some((ai, mode, money))
+}
+
+/// Parses a line like "1: Lv06 Skwovet : Tackle / Tail Whip". A move list of
+/// "Default Level Up" is filled in with `Species::default_moves`.
+fn parse_member(s: &str) -> Result<Pokemon, String> {
+    let malformed = || "malformed pokemon".to_string();
+    let mut parts = s.splitn(2, ": Lv");
+    parts.next().and_then(|slot| slot.parse::<u32>().ok()).ok_or_else(malformed)?;
+    let rest = parts.next().ok_or_else(malformed)?;
+
+    let mut parts = rest.splitn(2, ' ');
+    let level = parts.next().and_then(|level| level.parse::<u64>().ok()).ok_or_else(malformed)?;
+    let mut parts = parts.next().ok_or_else(malformed)?.splitn(2, " : ");
+    let species_name = form_name(parts.next().ok_or_else(malformed)?);
+    let moves = parts.next().ok_or_else(malformed)?;
+
+    let species = POKEDEX
+        .species(&species_name)
+        .ok_or_else(|| format!("unknown species \"{}\"", species_name))?;
+    let move_list: Vec<Move> = if moves == "Default Level Up" {
+        species.default_moves(level).into_iter().cloned().collect()
+    } else {
+        moves
+            .split(" / ")
+            .map(|name| {
+                POKEDEX
+                    .move_by_name(name)
+                    .cloned()
+                    .ok_or_else(|| format!("unknown move \"{}\"", name))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    Ok(Pokemon::new(species, level, move_list))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn load_trainers_test() {
+        let trainers = safe_load_trainers().expect("Could not load trainers");
+        assert_eq!(trainers.len(), 314);
+        assert!(trainers.iter().all(|trainer| trainer.id != 0));
+        assert_eq!(trainers.iter().filter(|t| t.mode == BattleMode::Doubles).count(), 21);
+    }
+
+    #[test]
+    fn parse_trainers_test() {
+        let text = "004 - Pokémon Trainer: Hop\r\nAI: 32 | Mode: Singles | Money: 1600\r\nPokémon Count: 2\r\n---\r\n1: Lv03 Wooloo : Tackle\r\n2: Lv05 Sobble : Pound / Growl\r\n---\r\n=========\r\n";
+        let trainers = parse_trainers(text).unwrap();
+        assert_eq!(trainers.len(), 1);
+        let hop = &trainers[0];
+        assert_eq!(hop.id, 4);
+        assert_eq!(hop.class, "Pokémon Trainer");
+        assert_eq!(hop.name, "Hop");
+        assert_eq!(hop.ai, 32);
+        assert_eq!(hop.mode, BattleMode::Singles);
+        assert_eq!(hop.money, 1600);
+
+        let sobble = &hop.party.members()[1];
+        assert_eq!(sobble.species_name(), "Sobble");
+        assert_eq!(sobble.level(), 5);
+        let moves: Vec<_> = sobble.moves().iter().map(|mv| mv.name()).collect();
+        assert_eq!(moves, vec!["Pound", "Growl"]);
+
+        let err = parse_trainers("004 - Pokémon Trainer: Hop\r\nAI: 32 | Mode: Triples | Money: 1600\r\nPokémon Count: 0").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn default_level_up_test() {
+        let victor = trainers_named("Victor");
+        let skwovet = &victor[0].party.members()[0];
+        assert_eq!(skwovet.species_name(), "Skwovet");
+        let expected: Vec<_> = skwovet.species().default_moves(5).iter().map(|mv| mv.name()).collect();
+        let moves: Vec<_> = skwovet.moves().iter().map(|mv| mv.name()).collect();
+        assert_eq!(moves, expected);
+        assert!(!moves.is_empty());
+    }
+}